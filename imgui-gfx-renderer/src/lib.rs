@@ -4,7 +4,7 @@ use gfx::pso::{PipelineData, PipelineState};
 use gfx::texture::{FilterMethod, SamplerInfo, WrapMode};
 use gfx::traits::FactoryExt;
 use gfx::{CommandBuffer, Encoder, Factory, IntoIndexBuffer, Rect, Resources, Slice};
-use imgui::{DrawList, FrameSize, ImDrawIdx, ImDrawVert, ImGui, ImTexture, Textures, Ui};
+use imgui::{DrawCmd, DrawList, FrameSize, ImDrawIdx, ImDrawVert, ImGui, ImTexture, Textures, Ui};
 
 pub type RendererResult<T> = Result<T, RendererError>;
 
@@ -255,6 +255,9 @@ impl<R: Resources> Renderer<R> {
         &mut self.textures
     }
 
+    /// Renders the frame to the render target with `encoder`.
+    ///
+    /// Draw list callbacks are called with the encoder as render target.
     pub fn render<'a, F: Factory<R>, C: CommandBuffer<R>>(
         &mut self,
         ui: Ui<'a>,
@@ -303,45 +306,55 @@ impl<R: Resources> Renderer<R> {
         self.upload_index_buffer(factory, encoder, draw_list.idx_buffer)?;
 
         self.bundle.slice.start = 0;
-        for cmd in draw_list.cmd_buffer {
-            let texture_id = cmd.texture_id.into();
-            let tex = self
-                .textures
-                .get(texture_id)
-                .ok_or_else(|| RendererError::BadTexture(texture_id))?;
-
-            self.bundle.slice.end = self.bundle.slice.start + cmd.elem_count;
-            let scissor = Rect {
-                x: cmd.clip_rect.x.max(0.0).min(fb_width).round() as u16,
-                y: cmd.clip_rect.y.max(0.0).min(fb_height).round() as u16,
-                w: (cmd.clip_rect.z - cmd.clip_rect.x)
-                    .abs()
-                    .min(fb_width)
-                    .round() as u16,
-                h: (cmd.clip_rect.w - cmd.clip_rect.y)
-                    .abs()
-                    .min(fb_height)
-                    .round() as u16,
-            };
-
-            #[cfg(feature = "directx")]
-            {
-                let constants = constants::Constants { matrix: *matrix };
-                encoder.update_constant_buffer(&self.constants, &constants);
+        for cmd in draw_list.commands() {
+            match cmd {
+                DrawCmd::Elements {
+                    count,
+                    clip_rect,
+                    texture_id,
+                } => {
+                    let tex = self
+                        .textures
+                        .get(texture_id)
+                        .ok_or_else(|| RendererError::BadTexture(texture_id))?;
+
+                    self.bundle.slice.end = self.bundle.slice.start + count as u32;
+                    let scissor = Rect {
+                        x: clip_rect.x.max(0.0).min(fb_width).round() as u16,
+                        y: clip_rect.y.max(0.0).min(fb_height).round() as u16,
+                        w: (clip_rect.z - clip_rect.x).abs().min(fb_width).round() as u16,
+                        h: (clip_rect.w - clip_rect.y).abs().min(fb_height).round() as u16,
+                    };
+
+                    #[cfg(feature = "directx")]
+                    {
+                        let constants = constants::Constants { matrix: *matrix };
+                        encoder.update_constant_buffer(&self.constants, &constants);
+                    }
+
+                    let data = pipe::BorrowedData {
+                        vertex_buffer: &self.bundle.vertex_buffer,
+                        #[cfg(not(feature = "directx"))]
+                        matrix,
+                        #[cfg(feature = "directx")]
+                        constants: &self.constants,
+                        tex,
+                        out: &self.bundle.out,
+                        scissor: &scissor,
+                    };
+                    encoder.draw(&self.bundle.slice, &self.bundle.pso, &data);
+                    self.bundle.slice.start = self.bundle.slice.end;
+                }
+                // Every draw call sets its whole state, so there is nothing to restore
+                DrawCmd::ResetRenderState => (),
+                DrawCmd::Callback {
+                    callback,
+                    clip_rect,
+                } => {
+                    let called = callback.call(encoder, clip_rect);
+                    debug_assert!(called, "Draw list callback expects another render target");
+                }
             }
-
-            let data = pipe::BorrowedData {
-                vertex_buffer: &self.bundle.vertex_buffer,
-                #[cfg(not(feature = "directx"))]
-                matrix,
-                #[cfg(feature = "directx")]
-                constants: &self.constants,
-                tex,
-                out: &self.bundle.out,
-                scissor: &scissor,
-            };
-            encoder.draw(&self.bundle.slice, &self.bundle.pso, &data);
-            self.bundle.slice.start = self.bundle.slice.end;
         }
         Ok(())
     }
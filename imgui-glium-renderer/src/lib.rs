@@ -4,7 +4,7 @@ use glium::program;
 use glium::texture;
 use glium::vertex;
 use glium::{uniform, DrawError, IndexBuffer, Program, Surface, Texture2d, VertexBuffer};
-use imgui::{DrawList, FrameSize, ImGui, ImTexture, Textures, Ui};
+use imgui::{DrawCmd, DrawList, FrameSize, ImGui, ImTexture, Textures, Ui};
 use std::borrow::Cow;
 use std::fmt;
 use std::rc::Rc;
@@ -70,6 +70,31 @@ pub struct Renderer {
     device_objects: DeviceObjects,
 }
 
+/// The render target passed to draw list callbacks.
+///
+/// The surface of [`Renderer::render`] may borrow data, so it can't be passed to callbacks.
+/// They draw with the glium context of the renderer instead.
+pub struct CallbackTarget {
+    ctx: Rc<Context>,
+    fb_size: (f32, f32),
+    matrix: [[f32; 4]; 4],
+}
+
+impl CallbackTarget {
+    /// The glium context of the renderer.
+    pub fn context(&self) -> &Rc<Context> {
+        &self.ctx
+    }
+    /// The size of the framebuffer in pixels.
+    pub fn framebuffer_size(&self) -> (f32, f32) {
+        self.fb_size
+    }
+    /// The projection matrix of the imgui vertices, which are in logical pixels.
+    pub fn matrix(&self) -> [[f32; 4]; 4] {
+        self.matrix
+    }
+}
+
 impl Renderer {
     pub fn init<F: Facade>(imgui: &mut ImGui, ctx: &F) -> RendererResult<Renderer> {
         let device_objects = DeviceObjects::init(imgui, ctx)?;
@@ -83,6 +108,9 @@ impl Renderer {
         &mut self.device_objects.textures
     }
 
+    /// Renders the frame into `surface`.
+    ///
+    /// Draw list callbacks are called with a [`CallbackTarget`].
     pub fn render<'a, S: Surface>(&mut self, surface: &mut S, ui: Ui<'a>) -> RendererResult<()> {
         let _ = self.ctx.insert_debug_marker("imgui-rs: starting rendering");
         let FrameSize {
@@ -103,10 +131,15 @@ impl Renderer {
             [0.0, 0.0, -1.0, 0.0],
             [-1.0, 1.0, 0.0, 1.0],
         ];
+        let mut target = CallbackTarget {
+            ctx: Rc::clone(&self.ctx),
+            fb_size,
+            matrix,
+        };
         let result = ui.render(|ui, mut draw_data| {
             draw_data.scale_clip_rects(ui.imgui().display_framebuffer_scale());
             for draw_list in &draw_data {
-                self.render_draw_list(surface, &draw_list, fb_size, matrix)?;
+                self.render_draw_list(surface, &mut target, &draw_list)?;
             }
             Ok(())
         });
@@ -117,13 +150,13 @@ impl Renderer {
     fn render_draw_list<'a, S: Surface>(
         &mut self,
         surface: &mut S,
+        target: &mut CallbackTarget,
         draw_list: &DrawList<'a>,
-        fb_size: (f32, f32),
-        matrix: [[f32; 4]; 4],
     ) -> RendererResult<()> {
         use glium::{Blend, DrawParameters, Rect};
 
-        let (fb_width, fb_height) = fb_size;
+        let (fb_width, fb_height) = target.fb_size;
+        let matrix = target.matrix;
 
         let vtx_buffer = VertexBuffer::immutable(&self.ctx, draw_list.vtx_buffer)?;
         let idx_buffer = IndexBuffer::immutable(
@@ -133,45 +166,58 @@ impl Renderer {
         )?;
 
         let mut idx_start = 0;
-        for cmd in draw_list.cmd_buffer {
-            let texture_id = cmd.texture_id.into();
-            let texture = self
-                .device_objects
-                .textures
-                .get(texture_id)
-                .ok_or_else(|| RendererError::BadTexture(texture_id))?;
-
-            let idx_end = idx_start + cmd.elem_count as usize;
-
-            surface.draw(
-                &vtx_buffer,
-                &idx_buffer
-                    .slice(idx_start..idx_end)
-                    .expect("Invalid index buffer range"),
-                &self.device_objects.program,
-                &uniform! {
-                    matrix: matrix,
-                    tex: texture.sampled()
-                },
-                &DrawParameters {
-                    blend: Blend::alpha_blending(),
-                    scissor: Some(Rect {
-                        left: cmd.clip_rect.x.max(0.0).min(fb_width).round() as u32,
-                        bottom: (fb_height - cmd.clip_rect.w).max(0.0).min(fb_width).round() as u32,
-                        width: (cmd.clip_rect.z - cmd.clip_rect.x)
-                            .abs()
-                            .min(fb_width)
-                            .round() as u32,
-                        height: (cmd.clip_rect.w - cmd.clip_rect.y)
-                            .abs()
-                            .min(fb_height)
-                            .round() as u32,
-                    }),
-                    ..DrawParameters::default()
-                },
-            )?;
-
-            idx_start = idx_end;
+        for cmd in draw_list.commands() {
+            match cmd {
+                DrawCmd::Elements {
+                    count,
+                    clip_rect,
+                    texture_id,
+                } => {
+                    let texture = self
+                        .device_objects
+                        .textures
+                        .get(texture_id)
+                        .ok_or_else(|| RendererError::BadTexture(texture_id))?;
+
+                    let idx_end = idx_start + count;
+
+                    surface.draw(
+                        &vtx_buffer,
+                        &idx_buffer
+                            .slice(idx_start..idx_end)
+                            .expect("Invalid index buffer range"),
+                        &self.device_objects.program,
+                        &uniform! {
+                            matrix: matrix,
+                            tex: texture.sampled()
+                        },
+                        &DrawParameters {
+                            blend: Blend::alpha_blending(),
+                            scissor: Some(Rect {
+                                left: clip_rect.x.max(0.0).min(fb_width).round() as u32,
+                                bottom: (fb_height - clip_rect.w).max(0.0).min(fb_height).round()
+                                    as u32,
+                                width: (clip_rect.z - clip_rect.x).abs().min(fb_width).round()
+                                    as u32,
+                                height: (clip_rect.w - clip_rect.y).abs().min(fb_height).round()
+                                    as u32,
+                            }),
+                            ..DrawParameters::default()
+                        },
+                    )?;
+
+                    idx_start = idx_end;
+                }
+                // Every draw call sets its whole state, so there is nothing to restore
+                DrawCmd::ResetRenderState => (),
+                DrawCmd::Callback {
+                    callback,
+                    clip_rect,
+                } => {
+                    let called = callback.call(target, clip_rect);
+                    debug_assert!(called, "Draw list callback expects another render target");
+                }
+            }
         }
 
         Ok(())
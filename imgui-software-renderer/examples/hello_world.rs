@@ -0,0 +1,58 @@
+//! Renders the hello world window without a GPU and saves it as `hello_world.png`.
+
+use imgui::*;
+use imgui_software_renderer::{Renderer, RgbaImage};
+
+const CLEAR_COLOR: [u8; 4] = [255, 255, 255, 255];
+
+fn main() {
+    let mut imgui = ImGui::init();
+    imgui.set_ini_filename(None);
+
+    let font_size = 13.0;
+    imgui.fonts().add_default_font_with_config(
+        ImFontConfig::new()
+            .oversample_h(1)
+            .pixel_snap_h(true)
+            .size_pixels(font_size),
+    );
+    imgui.fonts().add_font_with_config(
+        include_bytes!("../../resources/mplus-1p-regular.ttf"),
+        ImFontConfig::new()
+            .merge_mode(true)
+            .oversample_h(1)
+            .pixel_snap_h(true)
+            .size_pixels(font_size)
+            .rasterizer_multiply(1.75),
+        &FontGlyphRange::japanese(),
+    );
+
+    let mut renderer = Renderer::init(&mut imgui).expect("Failed to initialize renderer");
+
+    let frame_size = FrameSize::new(320.0, 120.0, 1.0);
+    let mut target = RgbaImage::new(320, 120);
+    target.clear(CLEAR_COLOR);
+
+    imgui.set_mouse_pos(0.0, 0.0);
+    let ui = imgui.frame(frame_size, 1.0 / 60.0);
+    ui.window(im_str!("Hello world"))
+        .position((10.0, 10.0), ImGuiCond::Always)
+        .size((300.0, 100.0), ImGuiCond::Always)
+        .build(|| {
+            ui.text(im_str!("Hello world!"));
+            ui.text(im_str!("こんにちは世界！"));
+            ui.text(im_str!("This...is...imgui-rs!"));
+            ui.separator();
+            let mouse_pos = ui.imgui().mouse_pos();
+            ui.text(im_str!(
+                "Mouse Position: ({:.1},{:.1})",
+                mouse_pos.0,
+                mouse_pos.1
+            ));
+        });
+    renderer.render(&mut target, ui).expect("Rendering failed");
+
+    target
+        .save_png("hello_world.png")
+        .expect("Failed to save hello_world.png");
+}
@@ -0,0 +1,569 @@
+use imgui::{DrawCmd, DrawList, FrameSize, ImDrawVert, ImGui, ImTexture, Textures, Ui};
+use png::HasParameters;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+pub type RendererResult<T> = Result<T, RendererError>;
+
+#[derive(Debug)]
+pub enum RendererError {
+    Io(io::Error),
+    PngEncoding(png::EncodingError),
+    PngDecoding(png::DecodingError),
+    UnsupportedPng(png::ColorType, png::BitDepth),
+    BadTexture(ImTexture),
+}
+
+impl fmt::Display for RendererError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::RendererError::*;
+        match *self {
+            Io(ref e) => write!(f, "I/O error: {}", e),
+            PngEncoding(ref e) => write!(f, "PNG encoding failed: {}", e),
+            PngDecoding(ref e) => write!(f, "PNG decoding failed: {}", e),
+            UnsupportedPng(color_type, bit_depth) => write!(
+                f,
+                "Unsupported PNG format: {:?} with {} bits per sample",
+                color_type, bit_depth as u8
+            ),
+            BadTexture(ref t) => write!(f, "Bad texture ID: {}", t.id()),
+        }
+    }
+}
+
+impl From<io::Error> for RendererError {
+    fn from(e: io::Error) -> RendererError {
+        RendererError::Io(e)
+    }
+}
+
+impl From<png::EncodingError> for RendererError {
+    fn from(e: png::EncodingError) -> RendererError {
+        RendererError::PngEncoding(e)
+    }
+}
+
+impl From<png::DecodingError> for RendererError {
+    fn from(e: png::DecodingError) -> RendererError {
+        RendererError::PngDecoding(e)
+    }
+}
+
+/// An 8-bit RGBA image stored in row-major order without padding.
+///
+/// Used both as the render target and as the texture type of the renderer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RgbaImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Creates a fully transparent image.
+    pub fn new(width: u32, height: u32) -> RgbaImage {
+        RgbaImage {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Creates an image from existing RGBA8 pixel data.
+    ///
+    /// Panics if `pixels` does not contain exactly `width * height * 4` bytes.
+    pub fn from_pixels(width: u32, height: u32, pixels: Vec<u8>) -> RgbaImage {
+        assert_eq!(
+            pixels.len(),
+            width as usize * height as usize * 4,
+            "Pixel data does not match the image dimensions ({}x{})",
+            width,
+            height
+        );
+        RgbaImage {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+    pub fn pixels_mut(&mut self) -> &mut [u8] {
+        &mut self.pixels
+    }
+    pub fn into_pixels(self) -> Vec<u8> {
+        self.pixels
+    }
+
+    /// Returns the RGBA value of a pixel.
+    ///
+    /// Panics if the coordinates are out of bounds.
+    pub fn get_pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = self.offset(x, y);
+        let p = &self.pixels[i..i + 4];
+        [p[0], p[1], p[2], p[3]]
+    }
+
+    /// Sets the RGBA value of a pixel.
+    ///
+    /// Panics if the coordinates are out of bounds.
+    pub fn put_pixel(&mut self, x: u32, y: u32, rgba: [u8; 4]) {
+        let i = self.offset(x, y);
+        self.pixels[i..i + 4].copy_from_slice(&rgba);
+    }
+
+    /// Fills the whole image with a single color.
+    pub fn clear(&mut self, rgba: [u8; 4]) {
+        for p in self.pixels.chunks_mut(4) {
+            p.copy_from_slice(&rgba);
+        }
+    }
+
+    /// Encodes the image as a PNG into the given writer.
+    pub fn write_png<W: Write>(&self, w: W) -> RendererResult<()> {
+        let mut encoder = png::Encoder::new(w, self.width, self.height);
+        encoder.set(png::ColorType::RGBA).set(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        Ok(())
+    }
+
+    /// Saves the image as a PNG file.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> RendererResult<()> {
+        let file = File::create(path)?;
+        self.write_png(BufWriter::new(file))
+    }
+
+    /// Decodes an 8-bit PNG image from the given reader.
+    ///
+    /// Grayscale, RGB and paletted images are converted to RGBA.
+    pub fn read_png<R: Read>(r: R) -> RendererResult<RgbaImage> {
+        let mut decoder = png::Decoder::new(r);
+        decoder.set(png::Transformations::EXPAND);
+        let (info, mut reader) = decoder.read_info()?;
+        let mut data = vec![0; info.buffer_size()];
+        reader.next_frame(&mut data)?;
+        if info.bit_depth != png::BitDepth::Eight {
+            return Err(RendererError::UnsupportedPng(
+                info.color_type,
+                info.bit_depth,
+            ));
+        }
+        let pixels = match info.color_type {
+            png::ColorType::RGBA => data,
+            png::ColorType::RGB => data
+                .chunks(3)
+                .flat_map(|p| vec![p[0], p[1], p[2], 255])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => data
+                .chunks(2)
+                .flat_map(|p| vec![p[0], p[0], p[0], p[1]])
+                .collect(),
+            png::ColorType::Grayscale => data.iter().flat_map(|&p| vec![p, p, p, 255]).collect(),
+            // EXPAND converts paletted images to RGB or RGBA
+            png::ColorType::Indexed => unreachable!(),
+        };
+        Ok(RgbaImage::from_pixels(info.width, info.height, pixels))
+    }
+
+    /// Loads a PNG file.
+    pub fn load_png<P: AsRef<Path>>(path: P) -> RendererResult<RgbaImage> {
+        let file = File::open(path)?;
+        RgbaImage::read_png(BufReader::new(file))
+    }
+
+    fn offset(&self, x: u32, y: u32) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "Pixel ({}, {}) is out of bounds ({}x{})",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+
+    fn sample(&self, u: f32, v: f32) -> [f32; 4] {
+        if self.width == 0 || self.height == 0 {
+            return [1.0; 4];
+        }
+        let x = ((u * self.width as f32).floor() as i64)
+            .max(0)
+            .min(self.width as i64 - 1);
+        let y = ((v * self.height as f32).floor() as i64)
+            .max(0)
+            .min(self.height as i64 - 1);
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        let p = &self.pixels[i..i + 4];
+        [
+            f32::from(p[0]) / 255.0,
+            f32::from(p[1]) / 255.0,
+            f32::from(p[2]) / 255.0,
+            f32::from(p[3]) / 255.0,
+        ]
+    }
+
+    fn blend(&mut self, x: usize, y: usize, src: [f32; 4]) {
+        let i = (y * self.width as usize + x) * 4;
+        let dst = &mut self.pixels[i..i + 4];
+        let a = src[3];
+        for c in 0..3 {
+            let d = f32::from(dst[c]) / 255.0;
+            dst[c] = to_u8(src[c] * a + d * (1.0 - a));
+        }
+        let d = f32::from(dst[3]) / 255.0;
+        dst[3] = to_u8(a + d * (1.0 - a));
+    }
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value.max(0.0).min(1.0) * 255.0).round() as u8
+}
+
+fn unpack_color(col: u32) -> [f32; 4] {
+    [
+        (col & 0xff) as f32 / 255.0,
+        ((col >> 8) & 0xff) as f32 / 255.0,
+        ((col >> 16) & 0xff) as f32 / 255.0,
+        ((col >> 24) & 0xff) as f32 / 255.0,
+    ]
+}
+
+pub type Texture = RgbaImage;
+
+/// Renders imgui draw data into an [`RgbaImage`] without any GPU context.
+///
+/// Triangles are rasterized with the top-left fill rule, so adjacent
+/// anti-aliasing fringes do not overlap, and textures are sampled with
+/// nearest-neighbour filtering. The output is fully deterministic.
+pub struct Renderer {
+    textures: Textures<Texture>,
+}
+
+impl Renderer {
+    pub fn init(imgui: &mut ImGui) -> RendererResult<Renderer> {
+        let texture = imgui.prepare_texture(|handle| {
+            RgbaImage::from_pixels(handle.width, handle.height, handle.pixels.to_vec())
+        });
+        let mut textures = Textures::new();
+        imgui.set_font_texture_id(textures.insert(texture));
+        Ok(Renderer { textures })
+    }
+
+    pub fn textures(&mut self) -> &mut Textures<Texture> {
+        &mut self.textures
+    }
+
+    /// Renders the frame into `target`.
+    ///
+    /// The target is expected to have the framebuffer size of the frame
+    /// (logical size multiplied by the hidpi factor); anything outside of it
+    /// is clipped. Draw list callbacks are called with the target.
+    pub fn render<'a>(&mut self, target: &mut RgbaImage, ui: Ui<'a>) -> RendererResult<()> {
+        let FrameSize {
+            logical_size: (width, height),
+            hidpi_factor,
+        } = ui.frame_size();
+        if !(width > 0.0 && height > 0.0) {
+            return Ok(());
+        }
+        let scale = hidpi_factor as f32;
+
+        ui.render(|ui, mut draw_data| {
+            draw_data.scale_clip_rects(ui.imgui().display_framebuffer_scale());
+            for draw_list in &draw_data {
+                self.render_draw_list(target, &draw_list, scale)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn render_draw_list<'a>(
+        &self,
+        target: &mut RgbaImage,
+        draw_list: &DrawList<'a>,
+        scale: f32,
+    ) -> RendererResult<()> {
+        let mut idx_start = 0;
+        for cmd in draw_list.commands() {
+            match cmd {
+                DrawCmd::Elements {
+                    count,
+                    clip_rect,
+                    texture_id,
+                } => {
+                    let texture = self
+                        .textures
+                        .get(texture_id)
+                        .ok_or(RendererError::BadTexture(texture_id))?;
+
+                    let idx_end = idx_start + count;
+                    let clip = [
+                        clip_rect.x.max(0.0),
+                        clip_rect.y.max(0.0),
+                        clip_rect.z.min(target.width as f32),
+                        clip_rect.w.min(target.height as f32),
+                    ];
+                    if clip[0] < clip[2] && clip[1] < clip[3] {
+                        for tri in draw_list.idx_buffer[idx_start..idx_end].chunks(3) {
+                            if tri.len() < 3 {
+                                break;
+                            }
+                            let vtx = [
+                                &draw_list.vtx_buffer[tri[0] as usize],
+                                &draw_list.vtx_buffer[tri[1] as usize],
+                                &draw_list.vtx_buffer[tri[2] as usize],
+                            ];
+                            rasterize_triangle(target, texture, vtx, scale, clip);
+                        }
+                    }
+
+                    idx_start = idx_end;
+                }
+                // The renderer has no state besides the target
+                DrawCmd::ResetRenderState => (),
+                DrawCmd::Callback {
+                    callback,
+                    clip_rect,
+                } => {
+                    let called = callback.call(target, clip_rect);
+                    debug_assert!(called, "Draw list callback expects another render target");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn edge(a: (f32, f32), b: (f32, f32), p: (f32, f32)) -> f32 {
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
+
+// Top-left fill rule: pixels exactly on an edge are only drawn if the edge is
+// a top or a left edge, so shared edges are never drawn twice.
+fn is_top_left(a: (f32, f32), b: (f32, f32)) -> bool {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    (dy == 0.0 && dx > 0.0) || dy < 0.0
+}
+
+fn rasterize_triangle(
+    target: &mut RgbaImage,
+    texture: &Texture,
+    vtx: [&ImDrawVert; 3],
+    scale: f32,
+    clip: [f32; 4],
+) {
+    let mut p = [
+        (vtx[0].pos.x * scale, vtx[0].pos.y * scale),
+        (vtx[1].pos.x * scale, vtx[1].pos.y * scale),
+        (vtx[2].pos.x * scale, vtx[2].pos.y * scale),
+    ];
+    let mut order = [0, 1, 2];
+    let mut area = edge(p[0], p[1], p[2]);
+    if area == 0.0 {
+        return;
+    }
+    if area < 0.0 {
+        p.swap(1, 2);
+        order.swap(1, 2);
+        area = -area;
+    }
+    let v = [vtx[order[0]], vtx[order[1]], vtx[order[2]]];
+    let col = [
+        unpack_color(v[0].col),
+        unpack_color(v[1].col),
+        unpack_color(v[2].col),
+    ];
+    let top_left = [
+        is_top_left(p[1], p[2]),
+        is_top_left(p[2], p[0]),
+        is_top_left(p[0], p[1]),
+    ];
+
+    let min_x = p[0].0.min(p[1].0).min(p[2].0).max(clip[0]).floor() as usize;
+    let min_y = p[0].1.min(p[1].1).min(p[2].1).max(clip[1]).floor() as usize;
+    let max_x = p[0].0.max(p[1].0).max(p[2].0).min(clip[2]).ceil() as usize;
+    let max_y = p[0].1.max(p[1].1).max(p[2].1).min(clip[3]).ceil() as usize;
+
+    for y in min_y..max_y {
+        let cy = y as f32 + 0.5;
+        if cy < clip[1] || cy >= clip[3] {
+            continue;
+        }
+        for x in min_x..max_x {
+            let cx = x as f32 + 0.5;
+            if cx < clip[0] || cx >= clip[2] {
+                continue;
+            }
+            let w = [
+                edge(p[1], p[2], (cx, cy)),
+                edge(p[2], p[0], (cx, cy)),
+                edge(p[0], p[1], (cx, cy)),
+            ];
+            let inside = (0..3).all(|i| w[i] > 0.0 || (w[i] == 0.0 && top_left[i]));
+            if !inside {
+                continue;
+            }
+            let l = [w[0] / area, w[1] / area, w[2] / area];
+            let u = l[0] * v[0].uv.x + l[1] * v[1].uv.x + l[2] * v[2].uv.x;
+            let t = l[0] * v[0].uv.y + l[1] * v[1].uv.y + l[2] * v[2].uv.y;
+            let texel = texture.sample(u, t);
+            let mut src = [0.0; 4];
+            for c in 0..4 {
+                let vc = l[0] * col[0][c] + l[1] * col[1][c] + l[2] * col[2][c];
+                src[c] = vc * texel[c];
+            }
+            target.blend(x, y, src);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use imgui::ImVec2;
+
+    const WHITE: u32 = 0xffff_ffff;
+    const RED: u32 = 0xff00_00ff;
+    const BLUE: u32 = 0xffff_0000;
+
+    fn white_texture() -> Texture {
+        RgbaImage::from_pixels(1, 1, vec![255; 4])
+    }
+
+    fn vert(x: f32, y: f32, u: f32, v: f32, col: u32) -> ImDrawVert {
+        ImDrawVert {
+            pos: ImVec2::new(x, y),
+            uv: ImVec2::new(u, v),
+            col,
+        }
+    }
+
+    // Draws a rectangle as two triangles sharing the diagonal, like dear imgui does
+    fn fill_rect(
+        target: &mut RgbaImage,
+        texture: &Texture,
+        min: (f32, f32),
+        max: (f32, f32),
+        col: u32,
+        clip: [f32; 4],
+    ) {
+        let a = vert(min.0, min.1, 0.0, 0.0, col);
+        let b = vert(max.0, min.1, 1.0, 0.0, col);
+        let c = vert(max.0, max.1, 1.0, 1.0, col);
+        let d = vert(min.0, max.1, 0.0, 1.0, col);
+        rasterize_triangle(target, texture, [&a, &b, &c], 1.0, clip);
+        rasterize_triangle(target, texture, [&a, &c, &d], 1.0, clip);
+    }
+
+    #[test]
+    fn shared_diagonal_is_drawn_once() {
+        let mut target = RgbaImage::new(4, 4);
+        let texture = white_texture();
+        // The pixel centers on the diagonal lie exactly on the shared edge, and would be
+        // blended twice (to 192) without the fill rule
+        let half_white = 0x80ff_ffff;
+        fill_rect(
+            &mut target,
+            &texture,
+            (0.0, 0.0),
+            (4.0, 4.0),
+            half_white,
+            [0.0, 0.0, 4.0, 4.0],
+        );
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(
+                    target.get_pixel(x, y),
+                    [128, 128, 128, 128],
+                    "({}, {})",
+                    x,
+                    y
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn shared_vertical_edge_belongs_to_the_right_rect() {
+        let mut target = RgbaImage::new(4, 1);
+        let texture = white_texture();
+        let clip = [0.0, 0.0, 4.0, 1.0];
+        // The pixel centers of column 2 lie on x = 2.5, a right edge of the red rectangle
+        // and a left edge of the blue one
+        fill_rect(&mut target, &texture, (2.5, 0.0), (4.0, 1.0), BLUE, clip);
+        fill_rect(&mut target, &texture, (0.0, 0.0), (2.5, 1.0), RED, clip);
+        assert_eq!(target.get_pixel(0, 0), [255, 0, 0, 255]);
+        assert_eq!(target.get_pixel(1, 0), [255, 0, 0, 255]);
+        assert_eq!(target.get_pixel(2, 0), [0, 0, 255, 255]);
+        assert_eq!(target.get_pixel(3, 0), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn clip_rect_limits_drawn_pixels() {
+        let mut target = RgbaImage::new(4, 4);
+        let texture = white_texture();
+        fill_rect(
+            &mut target,
+            &texture,
+            (0.0, 0.0),
+            (4.0, 4.0),
+            WHITE,
+            [1.0, 1.0, 3.0, 3.0],
+        );
+        for y in 0..4 {
+            for x in 0..4 {
+                let inside = x >= 1 && x < 3 && y >= 1 && y < 3;
+                let expected = if inside { [255; 4] } else { [0; 4] };
+                assert_eq!(target.get_pixel(x, y), expected, "({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn textures_are_sampled_without_filtering() {
+        let texels = [
+            [255, 0, 0, 255],
+            [0, 255, 0, 255],
+            [0, 0, 255, 255],
+            [255; 4],
+        ];
+        let texture = RgbaImage::from_pixels(2, 2, texels.concat());
+        let mut target = RgbaImage::new(4, 4);
+        fill_rect(
+            &mut target,
+            &texture,
+            (0.0, 0.0),
+            (4.0, 4.0),
+            WHITE,
+            [0.0, 0.0, 4.0, 4.0],
+        );
+        for y in 0..4 {
+            for x in 0..4 {
+                let texel = texels[(y / 2 * 2 + x / 2) as usize];
+                assert_eq!(target.get_pixel(x, y), texel, "({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn png_round_trip() {
+        let pixels = (0..3 * 2 * 4).map(|i| (i * 10) as u8).collect();
+        let image = RgbaImage::from_pixels(3, 2, pixels);
+        let mut png = Vec::new();
+        image.write_png(&mut png).unwrap();
+        let decoded = RgbaImage::read_png(&png[..]).unwrap();
+        assert_eq!(decoded, image);
+    }
+}
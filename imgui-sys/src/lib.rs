@@ -327,7 +327,7 @@ extern "C" {
     pub fn igPushIDInt(int_id: c_int);
     pub fn igPopID();
     pub fn igGetIDStr(str_id: *const c_char) -> ImGuiID;
-    pub fn igGetIDStrStr(str_id_begin: *const c_char, str_id_end: *const c_char) -> ImGuiID;
+    pub fn igGetIDRange(str_id_begin: *const c_char, str_id_end: *const c_char) -> ImGuiID;
     pub fn igGetIDPtr(ptr_id: *const c_void) -> ImGuiID;
 }
 
@@ -744,7 +744,7 @@ extern "C" {
         rounding: c_float,
         rounding_corners: ImDrawCornerFlags,
     );
-    pub fn ImDrawList_AddPolyLine(
+    pub fn ImDrawList_AddPolyline(
         this: *mut ImDrawList,
         points: *const ImVec2,
         num_points: c_int,
@@ -806,7 +806,7 @@ extern "C" {
         rect_min: ImVec2,
         rect_max: ImVec2,
         rounding: c_float,
-        rounding_corners_flags: c_int,
+        rounding_corners_flags: ImDrawCornerFlags,
     );
 
     pub fn ImDrawList_ChannelsSplit(this: *mut ImDrawList, channels_count: c_int);
@@ -0,0 +1,604 @@
+//! Golden-image regression testing for imgui user interfaces.
+//!
+//! A [`Harness`] drives frames with a fixed frame size and delta time, feeds
+//! scripted input into imgui and renders the result off-screen with
+//! `imgui-software-renderer`. The rendered image is then compared against a
+//! reference PNG with a [`Snapshot`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use imgui::*;
+//! use imgui_test_harness::{Harness, InputEvent, Snapshot, Tolerance};
+//!
+//! fn panel(ui: &Ui) {
+//!     ui.window(im_str!("Panel"))
+//!         .position((0.0, 0.0), ImGuiCond::Always)
+//!         .size((200.0, 100.0), ImGuiCond::Always)
+//!         .build(|| {
+//!             ui.button(im_str!("Press me"), (0.0, 0.0));
+//!         });
+//! }
+//!
+//! let mut harness = Harness::new(200.0, 100.0);
+//! harness.send(InputEvent::MouseMove(40.0, 30.0));
+//! harness.frame(panel);
+//! harness.send(InputEvent::MouseDown(ImMouseButton::Left));
+//! let image = harness.render(panel).unwrap();
+//!
+//! Snapshot::new("tests/snapshots/panel_pressed.png")
+//!     .tolerance(Tolerance::channel(2))
+//!     .assert_matches(&image);
+//! ```
+//!
+//! Set the `IMGUI_UPDATE_SNAPSHOTS` environment variable to write the
+//! rendered images as the new references instead of comparing them.
+
+use imgui::{FrameSize, ImGui, ImGuiKey, ImMouseButton, Ui};
+use imgui_software_renderer::{Renderer, RendererError, RgbaImage};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub use imgui_software_renderer::RgbaImage as Image;
+
+/// Environment variable that switches [`Snapshot::check`] to update mode.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "IMGUI_UPDATE_SNAPSHOTS";
+
+pub type HarnessResult<T> = Result<T, HarnessError>;
+
+#[derive(Debug)]
+pub enum HarnessError {
+    Renderer(RendererError),
+    Io(io::Error),
+    /// The reference image does not exist. The rendered image was written to
+    /// `actual` so it can be reviewed and renamed.
+    MissingReference {
+        reference: PathBuf,
+        actual: PathBuf,
+    },
+    /// The rendered image does not have the size of the reference image.
+    SizeMismatch {
+        reference: PathBuf,
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    /// More pixels than allowed differ from the reference image.
+    Mismatch {
+        reference: PathBuf,
+        mismatched_pixels: usize,
+        max_difference: u8,
+        diff: PathBuf,
+    },
+}
+
+impl fmt::Display for HarnessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::HarnessError::*;
+        match *self {
+            Renderer(ref e) => write!(f, "Rendering failed: {}", e),
+            Io(ref e) => write!(f, "I/O error: {}", e),
+            MissingReference {
+                ref reference,
+                ref actual,
+            } => write!(
+                f,
+                "Reference image {} does not exist (rendered image saved to {})",
+                reference.display(),
+                actual.display()
+            ),
+            SizeMismatch {
+                ref reference,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Size mismatch against {}: expected {}x{}, got {}x{}",
+                reference.display(),
+                expected.0,
+                expected.1,
+                actual.0,
+                actual.1
+            ),
+            Mismatch {
+                ref reference,
+                mismatched_pixels,
+                max_difference,
+                ref diff,
+            } => write!(
+                f,
+                "{} pixels differ from {} (max channel difference {}), see {}",
+                mismatched_pixels,
+                reference.display(),
+                max_difference,
+                diff.display()
+            ),
+        }
+    }
+}
+
+impl From<RendererError> for HarnessError {
+    fn from(e: RendererError) -> HarnessError {
+        HarnessError::Renderer(e)
+    }
+}
+
+impl From<io::Error> for HarnessError {
+    fn from(e: io::Error) -> HarnessError {
+        HarnessError::Io(e)
+    }
+}
+
+/// A single scripted input event.
+///
+/// Events are applied to the imgui IO state immediately and take effect in
+/// the next frame.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InputEvent {
+    MouseMove(f32, f32),
+    MouseDown(ImMouseButton),
+    MouseUp(ImMouseButton),
+    MouseWheel(f32),
+    KeyDown(ImGuiKey),
+    KeyUp(ImGuiKey),
+    Ctrl(bool),
+    Shift(bool),
+    Alt(bool),
+    Super(bool),
+    /// Text input. Dear imgui queues at most 16 characters per frame.
+    Char(char),
+}
+
+/// Drives imgui frames deterministically and renders them off-screen.
+pub struct Harness {
+    imgui: ImGui,
+    renderer: Renderer,
+    frame_size: FrameSize,
+    delta_time: f32,
+    clear_color: [u8; 4],
+}
+
+impl Harness {
+    /// Creates a harness with the default font, a hidpi factor of 1.0 and
+    /// a delta time of 1/60 s.
+    pub fn new(width: f64, height: f64) -> Harness {
+        let mut imgui = ImGui::init();
+        for (i, &key) in ImGuiKey::VARIANTS.iter().enumerate() {
+            imgui.set_imgui_key(key, i as u8);
+        }
+        Harness::with_imgui(imgui, FrameSize::new(width, height, 1.0))
+            .expect("Failed to initialize renderer")
+    }
+
+    /// Creates a harness around an already configured context.
+    ///
+    /// Fonts must be added before calling this, because the font atlas is
+    /// uploaded to the renderer here. Ini persistence is disabled so that
+    /// window layout cannot leak between test runs.
+    pub fn with_imgui(mut imgui: ImGui, frame_size: FrameSize) -> HarnessResult<Harness> {
+        imgui.set_ini_filename(None);
+        let renderer = Renderer::init(&mut imgui)?;
+        Ok(Harness {
+            imgui,
+            renderer,
+            frame_size,
+            delta_time: 1.0 / 60.0,
+            clear_color: [0, 0, 0, 255],
+        })
+    }
+
+    pub fn imgui(&mut self) -> &mut ImGui {
+        &mut self.imgui
+    }
+    pub fn renderer(&mut self) -> &mut Renderer {
+        &mut self.renderer
+    }
+    pub fn frame_size(&self) -> FrameSize {
+        self.frame_size
+    }
+    pub fn set_frame_size(&mut self, frame_size: FrameSize) {
+        self.frame_size = frame_size;
+    }
+    pub fn set_delta_time(&mut self, delta_time: f32) {
+        self.delta_time = delta_time;
+    }
+    /// Set the color the image is cleared to before rendering (default: opaque black).
+    pub fn set_clear_color(&mut self, rgba: [u8; 4]) {
+        self.clear_color = rgba;
+    }
+
+    /// Applies an input event to the state seen by the next frame.
+    pub fn send(&mut self, event: InputEvent) {
+        use self::InputEvent::*;
+        let imgui = &mut self.imgui;
+        match event {
+            MouseMove(x, y) => imgui.set_mouse_pos(x, y),
+            MouseDown(button) | MouseUp(button) => {
+                let mut states = imgui.mouse_down();
+                states[button as usize] = event == MouseDown(button);
+                imgui.set_mouse_down(states);
+            }
+            MouseWheel(value) => imgui.set_mouse_wheel(value),
+            KeyDown(key) => {
+                let index = imgui.get_key_index(key);
+                imgui.set_key(index as u8, true);
+            }
+            KeyUp(key) => {
+                let index = imgui.get_key_index(key);
+                imgui.set_key(index as u8, false);
+            }
+            Ctrl(value) => imgui.set_key_ctrl(value),
+            Shift(value) => imgui.set_key_shift(value),
+            Alt(value) => imgui.set_key_alt(value),
+            Super(value) => imgui.set_key_super(value),
+            Char(c) => imgui.add_input_character(c),
+        }
+    }
+
+    /// Applies a sequence of input events to the state seen by the next frame.
+    pub fn send_all<I: IntoIterator<Item = InputEvent>>(&mut self, events: I) {
+        for event in events {
+            self.send(event);
+        }
+    }
+
+    /// Runs a single frame without rendering it.
+    pub fn frame<F: FnOnce(&Ui)>(&mut self, f: F) {
+        let ui = self.imgui.frame(self.frame_size, self.delta_time);
+        f(&ui);
+        let _ = ui.render(|_, _| Ok::<(), ()>(()));
+    }
+
+    /// Runs `count` frames without rendering them.
+    ///
+    /// Useful to let auto-resizing windows settle before taking a snapshot.
+    pub fn frames<F: FnMut(&Ui)>(&mut self, count: usize, mut f: F) {
+        for _ in 0..count {
+            self.frame(&mut f);
+        }
+    }
+
+    /// Runs a single frame and renders it into a new image.
+    pub fn render<F: FnOnce(&Ui)>(&mut self, f: F) -> HarnessResult<RgbaImage> {
+        let FrameSize {
+            logical_size: (width, height),
+            hidpi_factor,
+        } = self.frame_size;
+        let mut image = RgbaImage::new(
+            (width * hidpi_factor).round() as u32,
+            (height * hidpi_factor).round() as u32,
+        );
+        image.clear(self.clear_color);
+
+        let ui = self.imgui.frame(self.frame_size, self.delta_time);
+        f(&ui);
+        self.renderer.render(&mut image, ui)?;
+        Ok(image)
+    }
+}
+
+/// Allowed difference between a rendered image and its reference.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tolerance {
+    /// Maximum absolute difference per color channel for a pixel to be
+    /// considered equal.
+    pub channel: u8,
+    /// Number of pixels that may exceed the channel tolerance.
+    pub pixels: usize,
+}
+
+impl Tolerance {
+    /// Requires the images to be identical.
+    pub fn exact() -> Tolerance {
+        Tolerance::default()
+    }
+    /// Allows each channel of each pixel to differ by up to `channel`.
+    pub fn channel(channel: u8) -> Tolerance {
+        Tolerance { channel, pixels: 0 }
+    }
+    /// Set the number of pixels that may exceed the channel tolerance.
+    pub fn pixels(mut self, pixels: usize) -> Tolerance {
+        self.pixels = pixels;
+        self
+    }
+}
+
+/// Result of a pixel-by-pixel image comparison.
+#[derive(Clone, Debug)]
+pub struct ImageDiff {
+    /// Number of pixels with a channel difference above the tolerance.
+    pub mismatched_pixels: usize,
+    /// Largest channel difference found in the whole image.
+    pub max_difference: u8,
+    /// Faded copy of the expected image with mismatched pixels in red.
+    pub image: RgbaImage,
+}
+
+/// Compares two images of the same size.
+///
+/// Panics if the image sizes differ.
+pub fn compare_images(
+    expected: &RgbaImage,
+    actual: &RgbaImage,
+    channel_tolerance: u8,
+) -> ImageDiff {
+    assert_eq!(
+        (expected.width(), expected.height()),
+        (actual.width(), actual.height()),
+        "Cannot compare images of different sizes"
+    );
+    let mut mismatched_pixels = 0;
+    let mut max_difference = 0;
+    let mut diff = Vec::with_capacity(expected.pixels().len());
+    for (e, a) in expected.pixels().chunks(4).zip(actual.pixels().chunks(4)) {
+        let difference = e
+            .iter()
+            .zip(a)
+            .map(|(&e, &a)| (i16::from(e) - i16::from(a)).abs() as u8)
+            .max()
+            .unwrap_or(0);
+        max_difference = max_difference.max(difference);
+        if difference > channel_tolerance {
+            mismatched_pixels += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            let luma = (u32::from(e[0]) * 3 + u32::from(e[1]) * 6 + u32::from(e[2])) / 10;
+            let faded = (128 + luma / 2) as u8;
+            diff.extend_from_slice(&[faded, faded, faded, 255]);
+        }
+    }
+    ImageDiff {
+        mismatched_pixels,
+        max_difference,
+        image: RgbaImage::from_pixels(expected.width(), expected.height(), diff),
+    }
+}
+
+/// A reference image stored on disk.
+///
+/// When a check fails, the rendered image is written next to the reference
+/// as `<name>.actual.png`, together with `<name>.diff.png` if the sizes match.
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    path: PathBuf,
+    tolerance: Tolerance,
+}
+
+impl Snapshot {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Snapshot {
+        Snapshot {
+            path: path.into(),
+            tolerance: Tolerance::exact(),
+        }
+    }
+    /// Set the allowed difference (default: exact match).
+    pub fn tolerance(mut self, tolerance: Tolerance) -> Snapshot {
+        self.tolerance = tolerance;
+        self
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Compares `actual` against the reference image.
+    ///
+    /// If the `IMGUI_UPDATE_SNAPSHOTS` environment variable is set, the
+    /// reference image is overwritten with `actual` instead.
+    pub fn check(&self, actual: &RgbaImage) -> HarnessResult<()> {
+        self.check_or_update(actual, env::var_os(UPDATE_SNAPSHOTS_VAR).is_some())
+    }
+
+    /// Like [`check`](#method.check), but panics with a description of the
+    /// failure. Intended to be used in `#[test]` functions.
+    pub fn assert_matches(&self, actual: &RgbaImage) {
+        if let Err(e) = self.check(actual) {
+            panic!("Snapshot check failed: {}", e);
+        }
+    }
+
+    fn check_or_update(&self, actual: &RgbaImage, update: bool) -> HarnessResult<()> {
+        let actual_path = self.sibling("actual");
+        let diff_path = self.sibling("diff");
+
+        if update {
+            if let Some(parent) = self.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            actual.save_png(&self.path)?;
+            return Ok(());
+        }
+        if !self.path.exists() {
+            actual.save_png(&actual_path)?;
+            return Err(HarnessError::MissingReference {
+                reference: self.path.clone(),
+                actual: actual_path,
+            });
+        }
+
+        let expected = RgbaImage::load_png(&self.path)?;
+        if (expected.width(), expected.height()) != (actual.width(), actual.height()) {
+            actual.save_png(&actual_path)?;
+            return Err(HarnessError::SizeMismatch {
+                reference: self.path.clone(),
+                expected: (expected.width(), expected.height()),
+                actual: (actual.width(), actual.height()),
+            });
+        }
+        let diff = compare_images(&expected, actual, self.tolerance.channel);
+        if diff.mismatched_pixels > self.tolerance.pixels {
+            actual.save_png(&actual_path)?;
+            diff.image.save_png(&diff_path)?;
+            return Err(HarnessError::Mismatch {
+                reference: self.path.clone(),
+                mismatched_pixels: diff.mismatched_pixels,
+                max_difference: diff.max_difference,
+                diff: diff_path,
+            });
+        }
+        // Clean up the output of a previous failed run
+        let _ = fs::remove_file(&actual_path);
+        let _ = fs::remove_file(&diff_path);
+        Ok(())
+    }
+
+    fn sibling(&self, suffix: &str) -> PathBuf {
+        let stem = self
+            .path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.path.with_file_name(format!("{}.{}.png", stem, suffix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> RgbaImage {
+        let mut image = RgbaImage::new(width, height);
+        image.clear(rgba);
+        image
+    }
+
+    // A fresh directory per test, so tests can run in parallel
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("imgui-test-harness-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn tolerance_builders() {
+        assert_eq!(
+            Tolerance::exact(),
+            Tolerance {
+                channel: 0,
+                pixels: 0
+            }
+        );
+        assert_eq!(
+            Tolerance::channel(3).pixels(5),
+            Tolerance {
+                channel: 3,
+                pixels: 5
+            }
+        );
+    }
+
+    #[test]
+    fn compare_identical_images() {
+        let image = solid(2, 2, [10, 20, 30, 255]);
+        let diff = compare_images(&image, &image, 0);
+        assert_eq!(diff.mismatched_pixels, 0);
+        assert_eq!(diff.max_difference, 0);
+    }
+
+    #[test]
+    fn compare_images_with_channel_tolerance() {
+        let expected = solid(2, 2, [100, 100, 100, 255]);
+        let mut actual = expected.clone();
+        actual.put_pixel(0, 0, [103, 100, 100, 255]);
+        actual.put_pixel(1, 1, [100, 90, 100, 255]);
+
+        let diff = compare_images(&expected, &actual, 3);
+        assert_eq!(diff.mismatched_pixels, 1);
+        assert_eq!(diff.max_difference, 10);
+        assert_eq!(diff.image.get_pixel(1, 1), [255, 0, 0, 255]);
+        assert_ne!(diff.image.get_pixel(0, 0), [255, 0, 0, 255]);
+
+        let diff = compare_images(&expected, &actual, 10);
+        assert_eq!(diff.mismatched_pixels, 0);
+        assert_eq!(diff.max_difference, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot compare images of different sizes")]
+    fn compare_images_of_different_sizes() {
+        compare_images(&solid(2, 2, [0; 4]), &solid(2, 3, [0; 4]), 0);
+    }
+
+    #[test]
+    fn check_missing_reference() {
+        let dir = temp_dir("missing");
+        let snapshot = Snapshot::new(dir.join("image.png"));
+        let actual = solid(2, 2, [1, 2, 3, 255]);
+        match snapshot.check_or_update(&actual, false) {
+            Err(HarnessError::MissingReference { actual: path, .. }) => {
+                assert_eq!(path, dir.join("image.actual.png"));
+                assert_eq!(RgbaImage::load_png(&path).unwrap(), actual);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        assert!(!snapshot.path().exists());
+    }
+
+    #[test]
+    fn check_size_mismatch() {
+        let dir = temp_dir("size");
+        let snapshot = Snapshot::new(dir.join("image.png"));
+        solid(2, 2, [0; 4]).save_png(snapshot.path()).unwrap();
+        match snapshot.check_or_update(&solid(3, 2, [0; 4]), false) {
+            Err(HarnessError::SizeMismatch {
+                expected, actual, ..
+            }) => {
+                assert_eq!(expected, (2, 2));
+                assert_eq!(actual, (3, 2));
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        assert!(dir.join("image.actual.png").exists());
+        assert!(!dir.join("image.diff.png").exists());
+    }
+
+    #[test]
+    fn check_pixel_mismatch() {
+        let dir = temp_dir("pixels");
+        let snapshot = Snapshot::new(dir.join("image.png"));
+        let expected = solid(2, 2, [0, 0, 0, 255]);
+        expected.save_png(snapshot.path()).unwrap();
+        let mut actual = expected.clone();
+        actual.put_pixel(1, 0, [50, 0, 0, 255]);
+
+        match snapshot.check_or_update(&actual, false) {
+            Err(HarnessError::Mismatch {
+                mismatched_pixels,
+                max_difference,
+                diff,
+                ..
+            }) => {
+                assert_eq!(mismatched_pixels, 1);
+                assert_eq!(max_difference, 50);
+                let diff = RgbaImage::load_png(&diff).unwrap();
+                assert_eq!(diff.get_pixel(1, 0), [255, 0, 0, 255]);
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        assert!(dir.join("image.actual.png").exists());
+
+        // Within the tolerance, and the output of the failed check is removed
+        let snapshot = snapshot.tolerance(Tolerance::exact().pixels(1));
+        snapshot.check_or_update(&actual, false).unwrap();
+        assert!(!dir.join("image.actual.png").exists());
+        assert!(!dir.join("image.diff.png").exists());
+    }
+
+    #[test]
+    fn update_writes_reference() {
+        let dir = temp_dir("update");
+        let snapshot = Snapshot::new(dir.join("nested").join("image.png"));
+        let actual = solid(2, 2, [4, 5, 6, 255]);
+        snapshot.check_or_update(&actual, true).unwrap();
+        assert_eq!(RgbaImage::load_png(snapshot.path()).unwrap(), actual);
+        snapshot.check_or_update(&actual, false).unwrap();
+
+        // Updating replaces a mismatching reference
+        let changed = solid(3, 3, [7, 8, 9, 255]);
+        snapshot.check_or_update(&changed, true).unwrap();
+        assert_eq!(RgbaImage::load_png(snapshot.path()).unwrap(), changed);
+    }
+}
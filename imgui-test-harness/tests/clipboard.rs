@@ -0,0 +1,40 @@
+use imgui::*;
+use imgui_test_harness::{Harness, InputEvent};
+
+fn focused_input(ui: &Ui, buf: &mut ImString) {
+    ui.window(im_str!("Input"))
+        .position((0.0, 0.0), ImGuiCond::Always)
+        .size((200.0, 100.0), ImGuiCond::Always)
+        .build(|| {
+            ui.set_keyboard_focus_here(0);
+            ui.input_text(im_str!("Text"), buf).build();
+        });
+}
+
+fn press_ctrl(harness: &mut Harness, buf: &mut ImString, key: ImGuiKey) {
+    harness.send_all(vec![InputEvent::Ctrl(true), InputEvent::KeyDown(key)]);
+    harness.frame(|ui| focused_input(ui, buf));
+    harness.send_all(vec![InputEvent::KeyUp(key), InputEvent::Ctrl(false)]);
+    harness.frame(|ui| focused_input(ui, buf));
+}
+
+#[test]
+fn copy_and_paste_use_the_backend() {
+    let mut harness = Harness::new(200.0, 100.0);
+    let clipboard = MemoryClipboard::new();
+    harness
+        .imgui()
+        .set_clipboard_backend(Box::new(clipboard.clone()));
+    let mut buf = ImString::with_capacity(32);
+    buf.push_str("hello");
+    harness.frame(|ui| focused_input(ui, &mut buf));
+
+    press_ctrl(&mut harness, &mut buf, ImGuiKey::A);
+    press_ctrl(&mut harness, &mut buf, ImGuiKey::C);
+    assert_eq!(clipboard.contents(), Some(ImString::new("hello")));
+
+    clipboard.set_contents(Some(ImString::new("world")));
+    press_ctrl(&mut harness, &mut buf, ImGuiKey::A);
+    press_ctrl(&mut harness, &mut buf, ImGuiKey::V);
+    assert_eq!(buf.to_str(), "world");
+}
@@ -0,0 +1,152 @@
+use imgui::*;
+use imgui_test_harness::{Harness, InputEvent};
+use std::borrow::Cow;
+use std::cell::RefCell;
+
+const ITEMS: [&str; 3] = ["Alpha", "Beta", "Gamma"];
+
+fn label<'a>(item: &'a &str) -> Cow<'a, ImStr> {
+    Cow::Owned(ImString::new(*item))
+}
+
+/// Returns a label function that records the screen position of each item.
+fn recording_label<'a>(
+    positions: &'a RefCell<Vec<(f32, f32)>>,
+    ui: &'a Ui,
+) -> impl for<'b> Fn(&'b &'static str) -> Cow<'b, ImStr> + 'a {
+    move |item| {
+        positions.borrow_mut().push(ui.get_cursor_screen_pos());
+        label(item)
+    }
+}
+
+fn click(harness: &mut Harness, (x, y): (f32, f32), mut f: impl FnMut(&Ui)) {
+    harness.send_all(vec![
+        InputEvent::MouseMove(x + 5.0, y + 5.0),
+        InputEvent::MouseDown(ImMouseButton::Left),
+    ]);
+    harness.frame(&mut f);
+    harness.send(InputEvent::MouseUp(ImMouseButton::Left));
+    harness.frame(&mut f);
+}
+
+fn window<F: FnOnce()>(ui: &Ui, f: F) {
+    ui.window(im_str!("Window"))
+        .position((0.0, 0.0), ImGuiCond::Always)
+        .size((200.0, 200.0), ImGuiCond::Always)
+        .build(f);
+}
+
+#[test]
+fn list_box_selects_clicked_item() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let positions = RefCell::new(Vec::new());
+    let mut current = 0;
+    harness.frame(|ui| {
+        window(ui, || {
+            ui.list_box_header(im_str!("List")).build_simple(
+                &ITEMS,
+                &mut current,
+                recording_label(&positions, ui),
+            );
+        })
+    });
+    let positions = positions.into_inner();
+    assert_eq!(positions.len(), ITEMS.len());
+    assert!(positions[0].1 < positions[1].1 && positions[1].1 < positions[2].1);
+
+    let mut changes = 0;
+    click(&mut harness, positions[2], |ui| {
+        window(ui, || {
+            if ui
+                .list_box_header(im_str!("List"))
+                .build_simple(&ITEMS, &mut current, label)
+            {
+                changes += 1;
+            }
+        })
+    });
+    assert_eq!(current, 2);
+    assert_eq!(changes, 1);
+}
+
+#[test]
+fn list_box_selects_value_missing_from_items() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let positions = RefCell::new(Vec::new());
+    let mut current = "Delta";
+    harness.frame(|ui| {
+        window(ui, || {
+            let changed = ui.list_box_header(im_str!("List")).build_simple_value(
+                &ITEMS,
+                &mut current,
+                recording_label(&positions, ui),
+            );
+            assert!(!changed);
+        })
+    });
+    assert_eq!(current, "Delta");
+
+    let position = positions.into_inner()[1];
+    click(&mut harness, position, |ui| {
+        window(ui, || {
+            ui.list_box_header(im_str!("List"))
+                .build_simple_value(&ITEMS, &mut current, label);
+        })
+    });
+    assert_eq!(current, "Beta");
+}
+
+#[test]
+fn combo_box_selects_item_in_popup() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let mut current = 0;
+    let mut combo_position = (0.0, 0.0);
+    harness.frame(|ui| {
+        window(ui, || {
+            combo_position = ui.get_cursor_screen_pos();
+            let changed = ui
+                .combo_box(im_str!("Combo"))
+                .build_simple(&ITEMS, &mut current, label);
+            assert!(!changed);
+        })
+    });
+
+    click(&mut harness, combo_position, |ui| {
+        window(ui, || {
+            ui.combo_box(im_str!("Combo"))
+                .build_simple(&ITEMS, &mut current, label);
+        })
+    });
+    // The popup is laid out once it has been open for a frame
+    let positions = RefCell::new(Vec::new());
+    harness.frame(|ui| {
+        window(ui, || {
+            ui.combo_box(im_str!("Combo")).build_simple(
+                &ITEMS,
+                &mut current,
+                recording_label(&positions, ui),
+            );
+        })
+    });
+    let positions = positions.into_inner();
+    // The preview value is labeled first
+    assert_eq!(positions.len(), 1 + ITEMS.len(), "Combo box is not open");
+
+    click(&mut harness, positions[2], |ui| {
+        window(ui, || {
+            ui.combo_box(im_str!("Combo"))
+                .build_simple(&ITEMS, &mut current, label);
+        })
+    });
+    assert_eq!(current, 1);
+
+    // The popup is closed once an item is selected
+    let mut open = true;
+    harness.frame(|ui| {
+        window(ui, || {
+            open = ui.combo_box(im_str!("Combo")).build(|| ());
+        })
+    });
+    assert!(!open);
+}
@@ -0,0 +1,144 @@
+use imgui::*;
+use imgui_test_harness::{Harness, InputEvent};
+
+#[derive(Debug, Default)]
+struct Frame {
+    button_pos: (f32, f32),
+    item_menu: bool,
+    window_menu: bool,
+    void_menu: bool,
+    popup_open: bool,
+    opening_pos: Option<(f32, f32)>,
+}
+
+/// A window with a button in the top left quarter of the screen.
+fn menu_frame(harness: &mut Harness, also_over_items: bool) -> Frame {
+    let mut frame = Frame::default();
+    harness.frame(|ui| {
+        ui.window(im_str!("Window"))
+            .position((0.0, 0.0), ImGuiCond::Always)
+            .size((100.0, 100.0), ImGuiCond::Always)
+            .build(|| {
+                frame.button_pos = ui.get_cursor_screen_pos();
+                ui.button(im_str!("Button"), (60.0, 20.0));
+                frame.item_menu = ui
+                    .context_menu_item()
+                    .str_id(im_str!("item menu"))
+                    .build(|| {
+                        frame.opening_pos = Some(ui.get_mouse_pos_on_opening_current_popup());
+                        ui.text("Item");
+                    });
+                frame.popup_open = ui.is_popup_open(im_str!("item menu"));
+                frame.window_menu = ui
+                    .context_menu_window()
+                    .also_over_items(also_over_items)
+                    .build(|| ui.text("Window"));
+            });
+        frame.void_menu = ui.context_menu_void().build(|| ui.text("Void"));
+    });
+    frame
+}
+
+fn click(harness: &mut Harness, (x, y): (f32, f32), button: ImMouseButton, also_over_items: bool) {
+    harness.send_all(vec![
+        InputEvent::MouseMove(x, y),
+        InputEvent::MouseDown(button),
+    ]);
+    menu_frame(harness, also_over_items);
+    harness.send(InputEvent::MouseUp(button));
+    menu_frame(harness, also_over_items);
+}
+
+#[test]
+fn item_context_menu_opens_on_right_click() {
+    // The window context menu would replace the item context menu otherwise
+    let also_over_items = false;
+    let mut harness = Harness::new(200.0, 200.0);
+    let frame = menu_frame(&mut harness, also_over_items);
+    assert!(!frame.item_menu && !frame.popup_open);
+    let (x, y) = frame.button_pos;
+    let pos = (x + 10.0, y + 10.0);
+
+    // Left clicks don't open the menu
+    click(&mut harness, pos, ImMouseButton::Left, also_over_items);
+    assert!(!menu_frame(&mut harness, also_over_items).item_menu);
+
+    click(&mut harness, pos, ImMouseButton::Right, also_over_items);
+    let frame = menu_frame(&mut harness, also_over_items);
+    assert!(frame.item_menu);
+    assert!(frame.popup_open);
+    assert!(!frame.window_menu);
+    assert!(!frame.void_menu);
+    assert_eq!(frame.opening_pos, Some(pos));
+}
+
+#[test]
+fn window_context_menu_can_ignore_items() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let (x, y) = menu_frame(&mut harness, false).button_pos;
+    click(
+        &mut harness,
+        (x + 70.0, y + 10.0),
+        ImMouseButton::Right,
+        false,
+    );
+    let frame = menu_frame(&mut harness, false);
+    assert!(frame.window_menu && !frame.item_menu);
+
+    // Close the menu by clicking outside of it, then right click the button
+    click(&mut harness, (150.0, 150.0), ImMouseButton::Left, false);
+    click(
+        &mut harness,
+        (x + 10.0, y + 10.0),
+        ImMouseButton::Right,
+        false,
+    );
+    let frame = menu_frame(&mut harness, false);
+    assert!(frame.item_menu && !frame.window_menu);
+}
+
+#[test]
+fn void_context_menu_opens_outside_of_windows() {
+    let mut harness = Harness::new(200.0, 200.0);
+    menu_frame(&mut harness, true);
+    click(&mut harness, (150.0, 150.0), ImMouseButton::Right, true);
+    let frame = menu_frame(&mut harness, true);
+    assert!(frame.void_menu);
+    assert!(!frame.item_menu && !frame.window_menu);
+}
+
+/// Returns whether the popup was opened by the click, whether it is open, and the position
+/// of the text that opens it.
+fn popup_frame(harness: &mut Harness) -> (bool, bool, (f32, f32)) {
+    let mut state = (false, false, (0.0, 0.0));
+    harness.frame(|ui| {
+        ui.window(im_str!("Window"))
+            .position((0.0, 0.0), ImGuiCond::Always)
+            .size((100.0, 100.0), ImGuiCond::Always)
+            .build(|| {
+                state.2 = ui.get_cursor_screen_pos();
+                ui.text("Text");
+                state.0 = ui.open_popup_on_item_click(im_str!("popup"), ImMouseButton::Left);
+                ui.popup(im_str!("popup"), || ui.text("Popup"));
+                state.1 = ui.is_popup_open(im_str!("popup"));
+            });
+    });
+    state
+}
+
+#[test]
+fn popup_opens_on_item_click() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let (opened, open, (x, y)) = popup_frame(&mut harness);
+    assert!(!opened && !open);
+    harness.send_all(vec![
+        InputEvent::MouseMove(x + 5.0, y + 5.0),
+        InputEvent::MouseDown(ImMouseButton::Left),
+    ]);
+    popup_frame(&mut harness);
+    harness.send(InputEvent::MouseUp(ImMouseButton::Left));
+    let (opened, open, _) = popup_frame(&mut harness);
+    assert!(opened && open);
+    let (opened, open, _) = popup_frame(&mut harness);
+    assert!(!opened && open);
+}
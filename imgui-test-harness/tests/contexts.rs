@@ -0,0 +1,125 @@
+use imgui::*;
+use imgui_test_harness::{Harness, Image};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn panel(ui: &Ui, title: &ImStr, clicks: usize) {
+    ui.window(title)
+        .position((10.0, 10.0), ImGuiCond::Always)
+        .size((180.0, 80.0), ImGuiCond::Always)
+        .build(|| {
+            ui.text(format!("Clicked {} times", clicks));
+            ui.button(im_str!("Click me"), (0.0, 0.0));
+        });
+}
+
+fn render_panel(title: &ImStr, clicks: usize) -> Image {
+    let mut harness = Harness::new(200.0, 100.0);
+    harness.frames(2, |ui| panel(ui, title, clicks));
+    harness.render(|ui| panel(ui, title, clicks)).unwrap()
+}
+
+#[test]
+fn imgui_is_send() {
+    fn assert_send<T: Send>() {}
+    assert_send::<ImGui>();
+    assert_send::<Arc<Mutex<SharedFontAtlas>>>();
+}
+
+#[test]
+fn contexts_on_one_thread_keep_their_own_state() {
+    let mut first = Harness::new(200.0, 100.0);
+    let mut second = Harness::new(200.0, 100.0);
+    for _ in 0..2 {
+        first.frame(|ui| panel(ui, im_str!("First"), 1));
+        second.frame(|ui| panel(ui, im_str!("Second"), 2));
+    }
+    first.frame(|ui| panel(ui, im_str!("First"), 1));
+    assert_eq!(first.imgui().get_frame_count(), 3);
+    assert_eq!(second.imgui().get_frame_count(), 2);
+
+    let first_image = first.render(|ui| panel(ui, im_str!("First"), 1)).unwrap();
+    let second_image = second.render(|ui| panel(ui, im_str!("Second"), 2)).unwrap();
+    assert_eq!(first_image, render_panel(im_str!("First"), 1));
+    assert_eq!(second_image, render_panel(im_str!("Second"), 2));
+}
+
+#[test]
+fn another_context_can_be_used_during_a_frame() {
+    let mut first = Harness::new(200.0, 100.0);
+    let mut second = Harness::new(200.0, 100.0);
+    second.frames(2, |_| ());
+    first.frames(2, |ui| panel(ui, im_str!("First"), 1));
+    let image = first
+        .render(|ui| {
+            let second = second.imgui();
+            assert_eq!(second.get_frame_count(), 2);
+            second.style_mut().alpha = 0.5;
+            panel(ui, im_str!("First"), 1);
+        })
+        .unwrap();
+    assert_eq!(image, render_panel(im_str!("First"), 1));
+    assert_eq!(second.imgui().style().alpha, 0.5);
+}
+
+#[test]
+#[should_panic(expected = "already in progress on this thread")]
+fn overlapping_frames_on_one_thread_panic() {
+    let mut first = Harness::new(200.0, 100.0);
+    let mut second = Harness::new(200.0, 100.0);
+    first.frame(|_| second.frame(|_| ()));
+}
+
+#[test]
+fn contexts_on_two_threads() {
+    let threads: Vec<_> = (0..2)
+        .map(|clicks| {
+            thread::spawn(move || {
+                let title = ImString::new(format!("Thread {}", clicks));
+                let mut harness = Harness::new(200.0, 100.0);
+                let mut images = Vec::new();
+                for _ in 0..20 {
+                    images.push(harness.render(|ui| panel(ui, &title, clicks)).unwrap());
+                }
+                images.pop().unwrap()
+            })
+        })
+        .collect();
+    for (clicks, thread) in threads.into_iter().enumerate() {
+        let title = ImString::new(format!("Thread {}", clicks));
+        assert_eq!(thread.join().unwrap(), render_panel(&title, clicks));
+    }
+}
+
+#[test]
+fn context_can_move_to_another_thread() {
+    let mut harness = Harness::new(200.0, 100.0);
+    harness.frame(|ui| panel(ui, im_str!("Moved"), 3));
+    let image = thread::spawn(move || {
+        harness.frame(|ui| panel(ui, im_str!("Moved"), 3));
+        harness.render(|ui| panel(ui, im_str!("Moved"), 3)).unwrap()
+    })
+    .join()
+    .unwrap();
+    assert_eq!(image, render_panel(im_str!("Moved"), 3));
+}
+
+#[test]
+fn contexts_share_a_font_atlas() {
+    let atlas = SharedFontAtlas::create();
+    atlas.lock().unwrap().fonts().add_default_font();
+    let frame_size = FrameSize::new(200.0, 100.0, 1.0);
+    let mut first = Harness::with_imgui(
+        ImGui::init_with_shared_font_atlas(atlas.clone()),
+        frame_size,
+    )
+    .unwrap();
+    let mut second =
+        Harness::with_imgui(ImGui::init_with_shared_font_atlas(atlas), frame_size).unwrap();
+    first.frames(2, |ui| panel(ui, im_str!("Shared"), 1));
+    second.frames(2, |ui| panel(ui, im_str!("Shared"), 1));
+    let first_image = first.render(|ui| panel(ui, im_str!("Shared"), 1)).unwrap();
+    let second_image = second.render(|ui| panel(ui, im_str!("Shared"), 1)).unwrap();
+    assert_eq!(first_image, second_image);
+    assert_eq!(first_image, render_panel(im_str!("Shared"), 1));
+}
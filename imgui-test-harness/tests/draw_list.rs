@@ -0,0 +1,241 @@
+use imgui::sys::{ImDrawIdx, ImDrawVert};
+use imgui::{DrawCmd, ImColor, ImTexture, ImVec2, ImVec4, WindowDrawList};
+use imgui_test_harness::Harness;
+
+/// The buffers and commands of the overlay draw list of a frame.
+struct Recorded {
+    vtx_buffer: Vec<ImDrawVert>,
+    idx_buffer: Vec<ImDrawIdx>,
+    // The element count, texture and clipping rectangle of each command
+    elements: Vec<(usize, ImTexture, ImVec4)>,
+}
+
+fn record_overlay<F: FnOnce(&WindowDrawList)>(harness: &mut Harness, f: F) -> Recorded {
+    let frame_size = harness.frame_size();
+    let ui = harness.imgui().frame(frame_size, 1.0 / 60.0);
+    f(&ui.get_overlay_draw_list());
+    let mut recorded = None;
+    ui.render(|_, draw_data| {
+        // The overlay is drawn on top of all windows
+        let draw_list = (&draw_data).into_iter().last().unwrap();
+        let elements = draw_list
+            .commands()
+            .filter_map(|cmd| match cmd {
+                DrawCmd::Elements {
+                    count,
+                    clip_rect,
+                    texture_id,
+                } => Some((count, texture_id, clip_rect)),
+                _ => None,
+            })
+            .collect();
+        recorded = Some(Recorded {
+            vtx_buffer: draw_list.vtx_buffer.to_vec(),
+            idx_buffer: draw_list.idx_buffer.to_vec(),
+            elements,
+        });
+        Ok::<(), ()>(())
+    })
+    .unwrap();
+    recorded.unwrap()
+}
+
+fn vtx(pos: (f32, f32), uv: (f32, f32), col: ImColor) -> ImDrawVert {
+    ImDrawVert {
+        pos: pos.into(),
+        uv: uv.into(),
+        col: col.into(),
+    }
+}
+
+fn assert_vertices_eq(actual: &[ImDrawVert], expected: &[ImDrawVert]) {
+    let fields = |v: &[ImDrawVert]| -> Vec<(ImVec2, ImVec2, u32)> {
+        v.iter().map(|v| (v.pos, v.uv, v.col)).collect()
+    };
+    assert_eq!(fields(actual), fields(expected));
+}
+
+#[test]
+fn prim_reserve_rect_uv() {
+    let mut harness = Harness::new(32.0, 32.0);
+    let red = ImColor::from([1.0, 0.0, 0.0]);
+    let recorded = record_overlay(&mut harness, |draw_list| {
+        let mut writer = draw_list.prim_reserve(6, 4);
+        writer.rect_uv((1.0, 2.0), (3.0, 4.0), (0.25, 0.5), (0.75, 1.0), red);
+        assert_eq!(writer.remaining_vtx(), 0);
+        assert_eq!(writer.remaining_idx(), 0);
+    });
+    assert_vertices_eq(
+        &recorded.vtx_buffer,
+        &[
+            vtx((1.0, 2.0), (0.25, 0.5), red),
+            vtx((3.0, 2.0), (0.75, 0.5), red),
+            vtx((3.0, 4.0), (0.75, 1.0), red),
+            vtx((1.0, 4.0), (0.25, 1.0), red),
+        ],
+    );
+    assert_eq!(recorded.idx_buffer, [0, 1, 2, 0, 2, 3]);
+}
+
+#[test]
+fn prim_reserve_quad_uv() {
+    let mut harness = Harness::new(32.0, 32.0);
+    let red = ImColor::from([1.0, 0.0, 0.0]);
+    let recorded = record_overlay(&mut harness, |draw_list| {
+        let mut writer = draw_list.prim_reserve(6, 4);
+        writer.quad_uv(
+            (2.0, 0.0),
+            (4.0, 2.0),
+            (2.0, 4.0),
+            (0.0, 2.0),
+            (0.5, 0.0),
+            (1.0, 0.5),
+            (0.5, 1.0),
+            (0.0, 0.5),
+            red,
+        );
+    });
+    assert_vertices_eq(
+        &recorded.vtx_buffer,
+        &[
+            vtx((2.0, 0.0), (0.5, 0.0), red),
+            vtx((4.0, 2.0), (1.0, 0.5), red),
+            vtx((2.0, 4.0), (0.5, 1.0), red),
+            vtx((0.0, 2.0), (0.0, 0.5), red),
+        ],
+    );
+    assert_eq!(recorded.idx_buffer, [0, 1, 2, 0, 2, 3]);
+}
+
+#[test]
+fn write_idx_is_relative_to_the_reserved_vertices() {
+    let mut harness = Harness::new(32.0, 32.0);
+    let green = ImColor::from([0.0, 1.0, 0.0]);
+    let recorded = record_overlay(&mut harness, |draw_list| {
+        // Takes the first 4 vertices
+        draw_list
+            .add_rect((0.0, 0.0), (8.0, 8.0), green)
+            .filled(true)
+            .build();
+        let mut writer = draw_list.prim_reserve(3, 3);
+        let uv = writer.tex_uv_white_pixel();
+        writer.write_vtx(vtx((10.0, 10.0), uv, green));
+        writer.write_vtx(vtx((20.0, 10.0), uv, green));
+        writer.write_vtx(vtx((10.0, 20.0), uv, green));
+        writer.write_idx(2);
+        writer.write_idx(0);
+        writer.write_idx(1);
+    });
+    assert_eq!(recorded.vtx_buffer.len(), 7);
+    assert_eq!(recorded.vtx_buffer[5].pos, ImVec2::new(20.0, 10.0));
+    assert_eq!(recorded.idx_buffer[6..], [6, 4, 5]);
+}
+
+#[test]
+fn unwritten_primitives_are_degenerate() {
+    let mut harness = Harness::new(32.0, 32.0);
+    let recorded = record_overlay(&mut harness, |draw_list| {
+        let _writer = draw_list.prim_reserve(6, 4);
+    });
+    assert_eq!(recorded.vtx_buffer.len(), 4);
+    assert_eq!(recorded.idx_buffer, [0; 6]);
+}
+
+#[test]
+#[should_panic(expected = "Only 0 indices and 0 vertices remain")]
+fn write_vtx_overflow() {
+    let mut harness = Harness::new(32.0, 32.0);
+    record_overlay(&mut harness, |draw_list| {
+        let mut writer = draw_list.prim_reserve(3, 3);
+        writer.write_idx(0);
+        writer.write_idx(1);
+        writer.write_idx(2);
+        for _ in 0..4 {
+            writer.write_vtx(ImDrawVert::default());
+        }
+    });
+}
+
+#[test]
+#[should_panic(expected = "Only 0 indices and 4 vertices remain")]
+fn rect_uv_overflow() {
+    let mut harness = Harness::new(32.0, 32.0);
+    record_overlay(&mut harness, |draw_list| {
+        let mut writer = draw_list.prim_reserve(6, 8);
+        writer.rect_uv((0.0, 0.0), (1.0, 1.0), (0.0, 0.0), (1.0, 1.0), [1.0; 4]);
+        writer.rect_uv((0.0, 0.0), (1.0, 1.0), (0.0, 0.0), (1.0, 1.0), [1.0; 4]);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Index 3 is out of the 3 reserved vertices!")]
+fn write_idx_out_of_range() {
+    let mut harness = Harness::new(32.0, 32.0);
+    record_overlay(&mut harness, |draw_list| {
+        draw_list.prim_reserve(3, 3).write_idx(3);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Index count (4) must be a multiple of 3!")]
+fn prim_reserve_partial_triangle() {
+    let mut harness = Harness::new(32.0, 32.0);
+    record_overlay(&mut harness, |draw_list| {
+        let _ = draw_list.prim_reserve(4, 4);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Cannot reserve 3 indices without vertices!")]
+fn prim_reserve_indices_without_vertices() {
+    let mut harness = Harness::new(32.0, 32.0);
+    record_overlay(&mut harness, |draw_list| {
+        let _ = draw_list.prim_reserve(3, 0);
+    });
+}
+
+#[test]
+#[should_panic(expected = "uses 16-bit indices")]
+fn prim_reserve_too_many_vertices() {
+    let mut harness = Harness::new(32.0, 32.0);
+    record_overlay(&mut harness, |draw_list| {
+        let _ = draw_list.prim_reserve(0, ImDrawIdx::max_value() as usize + 2);
+    });
+}
+
+#[test]
+fn channels_use_the_current_texture_and_clip_rect() {
+    let mut harness = Harness::new(32.0, 32.0);
+    let font_texture = ImTexture::from(harness.imgui().fonts().texture_id());
+    let texture = ImTexture::from(font_texture.id() + 1);
+    let full_screen = ImVec4::new(0.0, 0.0, 32.0, 32.0);
+    let clip_rect = ImVec4::new(8.0, 8.0, 24.0, 24.0);
+    let recorded = record_overlay(&mut harness, |draw_list| {
+        let rect = |p1: (f32, f32)| {
+            let p2 = (p1.0 + 4.0, p1.1 + 4.0);
+            draw_list.add_rect(p1, p2, [1.0; 4]).filled(true).build();
+        };
+        draw_list.channels_split(2, |channels| {
+            draw_list.with_texture(texture, || {
+                draw_list.with_clip_rect((8.0, 8.0), (24.0, 24.0), || {
+                    // Channel 1 still has the texture and clipping rectangle
+                    // of the split until it becomes current
+                    channels.set_current(1);
+                    rect((8.0, 8.0));
+                    channels.set_current(0);
+                    rect((12.0, 12.0));
+                });
+            });
+            rect((0.0, 0.0));
+        });
+    });
+    // Channel 0 is drawn first
+    assert_eq!(
+        recorded.elements,
+        [
+            (6, texture, clip_rect),
+            (6, font_texture, full_screen),
+            (6, texture, clip_rect),
+        ]
+    );
+}
@@ -0,0 +1,211 @@
+use imgui::*;
+use imgui_test_harness::{Harness, Image, Snapshot};
+use std::f32::consts::PI;
+use std::sync::{Arc, Mutex};
+
+fn snapshot(name: &str) -> Snapshot {
+    Snapshot::new(format!(
+        "{}/tests/snapshots/{}.png",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    ))
+}
+
+/// Renders the drawing of `f` in an undecorated window covering the whole frame.
+fn render_window<F: FnOnce(&WindowDrawList)>(harness: &mut Harness, f: F) -> Image {
+    let size = harness.frame_size().logical_size;
+    let size = (size.0 as f32, size.1 as f32);
+    harness
+        .render(|ui| {
+            ui.with_style_var(StyleVar::WindowBorderSize(0.0), || {
+                ui.window(im_str!("Draw"))
+                    .position((0.0, 0.0), ImGuiCond::Always)
+                    .size(size, ImGuiCond::Always)
+                    .flags(ImGuiWindowFlags::NoDecoration | ImGuiWindowFlags::NoBackground)
+                    .build(|| f(&ui.get_window_draw_list()));
+            });
+        })
+        .unwrap()
+}
+
+#[test]
+fn window_rect() {
+    let mut harness = Harness::new(32.0, 16.0);
+    let image = render_window(&mut harness, |draw_list| {
+        draw_list
+            .add_rect((4.0, 4.0), (20.0, 12.0), [1.0, 0.0, 0.0])
+            .filled(true)
+            .build();
+    });
+    snapshot("window_rect").assert_matches(&image);
+}
+
+#[test]
+fn window_path() {
+    let mut harness = Harness::new(64.0, 32.0);
+    let image = render_window(&mut harness, |draw_list| {
+        draw_list
+            .path([1.0, 0.0, 0.0])
+            .line_to((2.0, 2.0))
+            .line_to((14.0, 2.0))
+            .arc_to((14.0, 10.0), 8.0, -PI / 2.0, PI / 2.0, 8)
+            .line_to((2.0, 18.0))
+            .thickness(2.0)
+            .build();
+        draw_list
+            .path([0.0, 1.0, 0.0])
+            .line_to((26.0, 2.0))
+            .bezier_curve_to((40.0, 2.0), (26.0, 28.0), (40.0, 28.0), 8)
+            .closed(true)
+            .build();
+        draw_list
+            .path([0.0, 0.0, 1.0])
+            .rect((44.0, 4.0), (60.0, 28.0), 4.0)
+            .filled(true)
+            .build();
+    });
+    snapshot("window_path").assert_matches(&image);
+}
+
+#[test]
+fn window_polyline_and_quad() {
+    let mut harness = Harness::new(64.0, 32.0);
+    let image = render_window(&mut harness, |draw_list| {
+        let zigzag = [(2.0, 28.0), (8.0, 4.0), (14.0, 28.0), (20.0, 4.0)];
+        draw_list
+            .add_polyline(zigzag.iter().cloned(), [1.0, 0.0, 0.0])
+            .thickness(2.0)
+            .build();
+        let triangle = vec![(24.0, 28.0), (30.0, 4.0), (36.0, 28.0)];
+        draw_list
+            .add_polyline(triangle, [0.0, 1.0, 0.0])
+            .filled(true)
+            .build();
+        draw_list
+            .add_quad(
+                (50.0, 2.0),
+                (62.0, 16.0),
+                (50.0, 30.0),
+                (40.0, 16.0),
+                [0.0, 0.0, 1.0],
+            )
+            .thickness(2.0)
+            .build();
+    });
+    snapshot("window_polyline_and_quad").assert_matches(&image);
+}
+
+#[test]
+fn window_image() {
+    let mut harness = Harness::new(64.0, 32.0);
+    #[rustfmt::skip]
+    let pixels = vec![
+        255, 0, 0, 255,   0, 255, 0, 255,
+        0, 0, 255, 255,   255, 255, 255, 255,
+    ];
+    let texture = harness
+        .renderer()
+        .textures()
+        .insert(Image::from_pixels(2, 2, pixels));
+    let image = render_window(&mut harness, |draw_list| {
+        draw_list
+            .add_image(texture, (2.0, 2.0), (18.0, 18.0))
+            .build();
+        // Only the green half, tinted
+        draw_list
+            .add_image(texture, (20.0, 2.0), (28.0, 30.0))
+            .uv1((0.5, 0.0))
+            .color([1.0, 1.0, 1.0, 0.5])
+            .build();
+        draw_list
+            .add_image_quad(
+                texture,
+                (46.0, 2.0),
+                (60.0, 16.0),
+                (46.0, 30.0),
+                (32.0, 16.0),
+            )
+            .build();
+    });
+    snapshot("window_image").assert_matches(&image);
+}
+
+#[test]
+fn overlay_rect() {
+    let mut harness = Harness::new(32.0, 16.0);
+    let image = harness
+        .render(|ui| {
+            let draw_list = ui.get_overlay_draw_list();
+            draw_list
+                .add_rect((4.0, 4.0), (20.0, 12.0), [1.0, 0.0, 0.0])
+                .filled(true)
+                .build();
+        })
+        .unwrap();
+    snapshot("overlay_rect").assert_matches(&image);
+}
+
+#[test]
+fn overlay_is_drawn_on_top_of_windows() {
+    let mut harness = Harness::new(32.0, 32.0);
+    let draw = |ui: &Ui| {
+        ui.window(im_str!("Window"))
+            .position((0.0, 0.0), ImGuiCond::Always)
+            .size((32.0, 32.0), ImGuiCond::Always)
+            .bg_alpha(1.0)
+            .build(|| {
+                // Both draw lists can be used at the same time
+                let overlay = ui.get_overlay_draw_list();
+                let draw_list = ui.get_window_draw_list();
+                overlay
+                    .add_rect((8.0, 8.0), (24.0, 24.0), [1.0, 0.0, 0.0])
+                    .filled(true)
+                    .build();
+                draw_list
+                    .add_rect((0.0, 0.0), (32.0, 32.0), [0.0, 0.0, 1.0])
+                    .filled(true)
+                    .build();
+            });
+    };
+    // Windows are hidden in their first frame
+    harness.frame(draw);
+    let image = harness.render(draw).unwrap();
+    assert_eq!(image.get_pixel(16, 16), [255, 0, 0, 255]);
+    assert_eq!(image.get_pixel(6, 26), [0, 0, 255, 255]);
+}
+
+#[test]
+fn draw_callbacks_are_called_in_order() {
+    let mut harness = Harness::new(32.0, 16.0);
+    let clip_rects = Arc::new(Mutex::new(Vec::new()));
+    let image = harness
+        .render(|ui| {
+            let clip_rects = clip_rects.clone();
+            let draw_list = ui.get_overlay_draw_list();
+            draw_list
+                .add_rect((0.0, 0.0), (16.0, 16.0), [1.0, 0.0, 0.0])
+                .filled(true)
+                .build();
+            draw_list.add_callback(move |image: &mut Image, clip_rect| {
+                clip_rects.lock().unwrap().push(clip_rect);
+                image.put_pixel(4, 4, [0, 255, 0, 255]);
+                image.put_pixel(12, 12, [0, 255, 0, 255]);
+            });
+            draw_list.add_reset_render_state();
+            draw_list
+                .add_rect((8.0, 8.0), (16.0, 16.0), [0.0, 0.0, 1.0])
+                .filled(true)
+                .build();
+        })
+        .unwrap();
+    let clip_rects = clip_rects.lock().unwrap();
+    assert_eq!(clip_rects.len(), 1);
+    let clip_rect = clip_rects[0];
+    assert_eq!(
+        (clip_rect.x, clip_rect.y, clip_rect.z, clip_rect.w),
+        (0.0, 0.0, 32.0, 16.0)
+    );
+    // The callback draws over the red rectangle, and the blue one over the callback
+    assert_eq!(image.get_pixel(4, 4), [0, 255, 0, 255]);
+    assert_eq!(image.get_pixel(12, 12), [0, 0, 255, 255]);
+}
@@ -0,0 +1,96 @@
+use imgui::*;
+use imgui_test_harness::{Harness, InputEvent};
+
+fn focused_input<'ui, 'p, F>(ui: &Ui<'ui>, buf: &'p mut ImString, f: F)
+where
+    F: FnOnce(InputText<'ui, 'p>) -> InputText<'ui, 'p>,
+{
+    ui.window(im_str!("Input"))
+        .position((0.0, 0.0), ImGuiCond::Always)
+        .size((200.0, 100.0), ImGuiCond::Always)
+        .build(move || {
+            ui.set_keyboard_focus_here(0);
+            f(ui.input_text(im_str!("Text"), buf)).build();
+        });
+}
+
+fn type_text(harness: &mut Harness, text: &str) {
+    harness.send_all(text.chars().map(InputEvent::Char));
+}
+
+#[test]
+fn resize_buffer_grows_the_string() {
+    let mut harness = Harness::new(200.0, 100.0);
+    let mut buf = ImString::with_capacity(4);
+    harness.frame(|ui| focused_input(ui, &mut buf, |input| input.resize_buffer(true)));
+    type_text(&mut harness, "hello world");
+    harness.frame(|ui| focused_input(ui, &mut buf, |input| input.resize_buffer(true)));
+    assert_eq!(buf.to_str(), "hello world");
+}
+
+#[test]
+fn fixed_buffer_truncates_input() {
+    let mut harness = Harness::new(200.0, 100.0);
+    let mut buf = ImString::with_capacity(4);
+    harness.frame(|ui| focused_input(ui, &mut buf, |input| input));
+    type_text(&mut harness, "hello world");
+    harness.frame(|ui| focused_input(ui, &mut buf, |input| input));
+    assert_eq!(buf.to_str(), "hell");
+}
+
+struct UppercaseLetters;
+
+impl InputTextCallbackHandler for UppercaseLetters {
+    fn char_filter(&mut self, c: char) -> Option<char> {
+        if c.is_alphabetic() {
+            c.to_uppercase().next()
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn char_filter_replaces_and_discards_characters() {
+    let mut harness = Harness::new(200.0, 100.0);
+    let mut buf = ImString::with_capacity(16);
+    let mut handler = UppercaseLetters;
+    harness.frame(|ui| focused_input(ui, &mut buf, |input| input));
+    type_text(&mut harness, "a1b2 é");
+    harness.frame(|ui| {
+        focused_input(ui, &mut buf, |input| {
+            input
+                .callback_char_filter(true)
+                .callback_handler(&mut handler)
+        })
+    });
+    assert_eq!(buf.to_str(), "ABÉ");
+}
+
+struct History(&'static str);
+
+impl InputTextCallbackHandler for History {
+    fn on_history(&mut self, direction: HistoryDirection, mut data: TextCallbackData) {
+        if direction == HistoryDirection::Up {
+            data.replace_text(self.0);
+        }
+    }
+}
+
+#[test]
+fn history_callback_replaces_the_text() {
+    let mut harness = Harness::new(200.0, 100.0);
+    let mut buf = ImString::new("draft");
+    let mut handler = History("a previous command");
+    harness.frame(|ui| focused_input(ui, &mut buf, |input| input));
+    harness.send(InputEvent::KeyDown(ImGuiKey::UpArrow));
+    harness.frame(|ui| {
+        focused_input(ui, &mut buf, |input| {
+            input
+                .resize_buffer(true)
+                .callback_history(true)
+                .callback_handler(&mut handler)
+        })
+    });
+    assert_eq!(buf.to_str(), "a previous command");
+}
@@ -0,0 +1,44 @@
+use imgui::*;
+use imgui_test_harness::Harness;
+use std::ops::Range;
+
+fn clipped_ranges(items_height: Option<f32>) -> Vec<Range<usize>> {
+    let mut harness = Harness::new(200.0, 100.0);
+    let mut ranges = Vec::new();
+    harness.frame(|ui| {
+        ui.window(im_str!("List"))
+            .position((0.0, 0.0), ImGuiCond::Always)
+            .size((200.0, 100.0), ImGuiCond::Always)
+            .build(|| {
+                let clipper = ui.list_clipper(1000);
+                let clipper = match items_height {
+                    Some(height) => clipper.items_height(height),
+                    None => clipper,
+                };
+                clipper.build(|range| {
+                    for i in range.clone() {
+                        ui.text(format!("Item {}", i));
+                    }
+                    ranges.push(range);
+                });
+            });
+    });
+    ranges
+}
+
+#[test]
+fn only_visible_items_are_submitted() {
+    let ranges = clipped_ranges(Some(17.0));
+    assert_eq!(ranges.len(), 1);
+    assert_eq!(ranges[0].start, 0);
+    assert!(ranges[0].end > 1 && ranges[0].end < 10, "{:?}", ranges);
+}
+
+#[test]
+fn first_item_is_measured_without_items_height() {
+    let ranges = clipped_ranges(None);
+    assert_eq!(ranges.len(), 2);
+    assert_eq!(ranges[0], 0..1);
+    assert_eq!(ranges[1].start, 1);
+    assert!(ranges[1].end > 2 && ranges[1].end < 10, "{:?}", ranges);
+}
@@ -0,0 +1,72 @@
+use imgui::*;
+use imgui_test_harness::Harness;
+use std::env;
+use std::fs;
+use std::process;
+
+fn window<F: FnOnce()>(ui: &Ui, f: F) {
+    ui.window(im_str!("Window"))
+        .position((0.0, 0.0), ImGuiCond::Always)
+        .size((200.0, 200.0), ImGuiCond::Always)
+        .build(f);
+}
+
+fn log_tree(harness: &mut Harness, target: LogTarget, max_depth: Option<u32>) -> Option<String> {
+    let mut text = None;
+    harness.frame(|ui| {
+        window(ui, || {
+            text = ui.log_to(target, max_depth, || {
+                ui.text("Hello");
+                ui.tree_node(im_str!("Node")).build(|| ui.text("Child"));
+            });
+        })
+    });
+    text
+}
+
+#[test]
+fn buffer_captures_the_text() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let text = log_tree(&mut harness, LogTarget::Buffer, None);
+    // Closed tree nodes are opened while logging
+    assert_eq!(text.unwrap(), "\nHello\n> Node\n    Child\n");
+}
+
+#[test]
+fn tree_nodes_deeper_than_max_depth_are_closed() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let text = log_tree(&mut harness, LogTarget::Buffer, Some(0));
+    assert_eq!(text.unwrap(), "\nHello\n> Node\n");
+}
+
+#[test]
+fn buffer_capture_leaves_the_clipboard_alone() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let clipboard = MemoryClipboard::new();
+    clipboard.set_contents(Some(ImString::new("before")));
+    harness
+        .imgui()
+        .set_clipboard_backend(Box::new(clipboard.clone()));
+
+    log_tree(&mut harness, LogTarget::Buffer, None);
+    assert_eq!(clipboard.contents(), Some(ImString::new("before")));
+
+    let text = log_tree(&mut harness, LogTarget::Clipboard, Some(0));
+    assert_eq!(text, None);
+    assert_eq!(
+        clipboard.contents(),
+        Some(ImString::new("\nHello\n> Node\n"))
+    );
+}
+
+#[test]
+fn file_target_writes_the_text() {
+    let path = env::temp_dir().join(format!("imgui-log-{}.txt", process::id()));
+    let filename = ImString::new(path.to_str().unwrap());
+    let mut harness = Harness::new(200.0, 200.0);
+    let text = log_tree(&mut harness, LogTarget::File(Some(&filename)), Some(0));
+    assert_eq!(text, None);
+    let contents = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(contents, "\nHello\n> Node\n");
+}
@@ -0,0 +1,91 @@
+use imgui::*;
+use imgui_test_harness::{Harness, InputEvent};
+
+/// Submits `lines` lines in a child frame, and returns its scroll position and range as of the
+/// previous frame.
+fn log_frame(harness: &mut Harness, lines: usize, auto_scroll: &mut AutoScroll) -> (f32, f32) {
+    let mut scroll = (0.0, 0.0);
+    harness.frame(|ui| {
+        ui.window(im_str!("Window"))
+            .position((0.0, 0.0), ImGuiCond::Always)
+            .size((200.0, 200.0), ImGuiCond::Always)
+            .build(|| {
+                ui.child_frame(im_str!("log"), (0.0, 100.0))
+                    .auto_scroll(auto_scroll)
+                    .build(|| {
+                        scroll = (ui.get_scroll_y(), ui.get_scroll_max_y());
+                        for line in 0..lines {
+                            ui.text(format!("Line {}", line));
+                        }
+                    });
+            });
+    });
+    scroll
+}
+
+#[test]
+fn window_scroll_position_is_set() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let mut scroll = (0.0, 0.0);
+    for frame in 0..3 {
+        harness.frame(|ui| {
+            ui.window(im_str!("Window"))
+                .position((0.0, 0.0), ImGuiCond::Always)
+                .size((200.0, 100.0), ImGuiCond::Always)
+                .build(|| {
+                    scroll = (ui.get_scroll_y(), ui.get_scroll_max_y());
+                    for line in 0..50 {
+                        ui.text(format!("Line {}", line));
+                    }
+                    if frame == 1 {
+                        ui.set_scroll_y(40.0);
+                    }
+                });
+        });
+    }
+    assert_eq!(scroll.0, 40.0);
+    assert!(scroll.1 > 40.0);
+}
+
+#[test]
+fn auto_scroll_follows_new_content() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let mut auto_scroll = AutoScroll::new();
+    let mut max_y = 0.0;
+    for lines in 10..30 {
+        let (scroll_y, scroll_max_y) = log_frame(&mut harness, lines, &mut auto_scroll);
+        assert_eq!(scroll_y, scroll_max_y);
+        assert!(auto_scroll.is_at_bottom());
+        max_y = scroll_max_y;
+    }
+    assert!(max_y > 0.0);
+}
+
+#[test]
+fn auto_scroll_stops_when_scrolled_up() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let mut auto_scroll = AutoScroll::new();
+    for lines in 10..=30 {
+        log_frame(&mut harness, lines, &mut auto_scroll);
+    }
+    // The user scrolls up with the mouse wheel after the scroll to the last appended line
+    log_frame(&mut harness, 30, &mut auto_scroll);
+    harness.send_all(vec![
+        InputEvent::MouseMove(50.0, 50.0),
+        InputEvent::MouseWheel(100.0),
+    ]);
+    log_frame(&mut harness, 30, &mut auto_scroll);
+    harness.send(InputEvent::MouseWheel(0.0));
+    for lines in 31..35 {
+        let (scroll_y, scroll_max_y) = log_frame(&mut harness, lines, &mut auto_scroll);
+        assert_eq!(scroll_y, 0.0);
+        assert!(scroll_max_y > 0.0);
+        assert!(!auto_scroll.is_at_bottom());
+    }
+
+    auto_scroll.scroll_to_bottom();
+    log_frame(&mut harness, 35, &mut auto_scroll);
+    let (scroll_y, scroll_max_y) = log_frame(&mut harness, 36, &mut auto_scroll);
+    assert_eq!(scroll_y, scroll_max_y);
+    assert!(auto_scroll.is_at_bottom());
+}
@@ -0,0 +1,63 @@
+use imgui::*;
+use imgui_test_harness::Harness;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Panels {
+    show_log: bool,
+}
+
+impl KeyValueSettings for Panels {
+    fn read(&mut self, key: &str, value: &str) {
+        if key == "ShowLog" {
+            self.show_log = value == "1";
+        }
+    }
+    fn write(&self, out: &mut SettingsWriter) {
+        out.key_value("ShowLog", self.show_log as i32);
+    }
+}
+
+#[test]
+fn changed_settings_are_saved_after_the_saving_rate() {
+    let mut harness = Harness::new(100.0, 100.0);
+    harness.set_delta_time(0.25);
+    let panels = Arc::new(Mutex::new(Panels::default()));
+    {
+        let imgui = harness.imgui();
+        imgui.set_ini_saving_rate(1.0);
+        imgui.add_settings("MyApp", "Panels", panels.clone());
+    }
+    harness.frame(|_| ());
+
+    panels.lock().unwrap().show_log = true;
+    harness.imgui().mark_ini_settings_dirty();
+    harness.frames(3, |_| ());
+    assert!(!harness.imgui().want_save_ini_settings());
+    harness.frame(|_| ());
+    assert!(harness.imgui().want_save_ini_settings());
+
+    let ini = harness.imgui().save_ini_settings();
+    assert!(ini.contains("[MyApp][Panels]\nShowLog=1\n"), "{}", ini);
+    assert!(!harness.imgui().want_save_ini_settings());
+}
+
+#[test]
+fn settings_are_loaded_with_the_window_settings() {
+    let mut harness = Harness::new(100.0, 100.0);
+    let panels = Arc::new(Mutex::new(Panels::default()));
+    harness
+        .imgui()
+        .add_settings("MyApp", "Panels", panels.clone());
+    harness
+        .imgui()
+        .load_ini_settings("[Window][Main]\nPos=10,20\n\n[MyApp][Panels]\nShowLog=1\n");
+    assert!(panels.lock().unwrap().show_log);
+
+    let mut pos = (0.0, 0.0);
+    harness.frame(|ui| {
+        ui.window(im_str!("Main"))
+            .build(|| pos = ui.get_window_pos());
+    });
+    assert_eq!(pos, (10.0, 20.0));
+}
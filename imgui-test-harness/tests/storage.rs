@@ -0,0 +1,83 @@
+use imgui::*;
+use imgui_test_harness::Harness;
+
+/// Runs a frame with a mutable `Ui`, which `Ui::storage` needs.
+fn frame<F: FnOnce(&mut Ui)>(harness: &mut Harness, f: F) {
+    let frame_size = harness.frame_size();
+    let mut ui = harness.imgui().frame(frame_size, 1.0 / 60.0);
+    f(&mut ui);
+    let _ = ui.render(|_, _| Ok::<(), ()>(()));
+}
+
+fn window<'ui, 'p>(ui: &Ui<'ui>, name: &'p ImStr) -> Window<'ui, 'p> {
+    ui.window(name)
+        .position((0.0, 0.0), ImGuiCond::Always)
+        .size((100.0, 100.0), ImGuiCond::Always)
+}
+
+#[test]
+fn ids_depend_on_the_id_stack() {
+    let mut harness = Harness::new(200.0, 200.0);
+    harness.frame(|ui| {
+        let id = ui.get_id("a");
+        assert_eq!(ui.get_id("a"), id);
+        assert_ne!(ui.get_id("b"), id);
+        assert_ne!(ui.get_id(1), ui.get_id(2));
+        ui.with_id("parent", || assert_ne!(ui.get_id("a"), id));
+        // Only the given part of the string is hashed
+        assert_eq!(ui.get_id(&"ab"[..1]), id);
+    });
+}
+
+#[test]
+fn window_storage_persists_across_frames() {
+    let mut harness = Harness::new(200.0, 200.0);
+    for expected in 0..3 {
+        frame(&mut harness, |ui| {
+            window(ui, im_str!("A")).build(|| {
+                let id = ui.get_id("counter");
+                let count = ui.storage().get_i32(id, 0);
+                assert_eq!(count, expected);
+                ui.storage().set_i32(id, count + 1);
+            });
+        });
+    }
+    frame(&mut harness, |ui| {
+        window(ui, im_str!("B")).build(|| {
+            let id = ui.get_id("counter");
+            assert_eq!(ui.storage().get_i32(id, 0), 0);
+        });
+    });
+}
+
+#[test]
+fn state_storage_replaces_the_window_storage() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let mut state = StateStorage::new();
+    let mut node_id = 0;
+    frame(&mut harness, |ui| {
+        window(ui, im_str!("A")).build(|| {
+            node_id = ui.get_id("node");
+            ui.with_state_storage(&mut state, || {
+                ui.tree_node(im_str!("node"))
+                    .opened(true, ImGuiCond::Always)
+                    .build(|| ());
+            });
+            // Tree nodes store whether they are open
+            assert_eq!(ui.storage().get_i32(node_id, 0), 0);
+        });
+    });
+    assert_eq!(state.storage().get_i32(node_id, 0), 1);
+
+    // The state is reset by clearing the storage
+    state.storage().clear();
+    let mut opened = false;
+    frame(&mut harness, |ui| {
+        window(ui, im_str!("A")).build(|| {
+            ui.with_state_storage(&mut state, || {
+                ui.tree_node(im_str!("node")).build(|| opened = true);
+            });
+        });
+    });
+    assert!(!opened);
+}
@@ -0,0 +1,169 @@
+use imgui::*;
+use imgui_test_harness::{Harness, InputEvent};
+
+fn columns() -> [TableColumn<'static>; 3] {
+    [
+        TableColumn::new(im_str!("Name")).sortable(true).width(80.0),
+        TableColumn::new(im_str!("Size")).sortable(true).width(60.0),
+        TableColumn::new(im_str!("Kind")),
+    ]
+}
+
+fn window<F: FnOnce()>(ui: &Ui, f: F) {
+    ui.window(im_str!("Window"))
+        .position((0.0, 0.0), ImGuiCond::Always)
+        .size((300.0, 200.0), ImGuiCond::Always)
+        .build(f);
+}
+
+/// Screen position of the header of each column, as seen from the table body.
+fn header_positions(ui: &Ui, header_y: f32, count: usize) -> Vec<(f32, f32)> {
+    let window_x = ui.get_window_pos().0;
+    (0..count)
+        .map(|column| {
+            let x = window_x + ui.get_column_offset(column as i32);
+            (x, header_y)
+        })
+        .collect()
+}
+
+fn click(harness: &mut Harness, (x, y): (f32, f32)) {
+    harness.send_all(vec![
+        InputEvent::MouseMove(x + 10.0, y + 5.0),
+        InputEvent::MouseDown(ImMouseButton::Left),
+    ]);
+}
+
+fn release(harness: &mut Harness) {
+    harness.send(InputEvent::MouseUp(ImMouseButton::Left));
+}
+
+#[derive(Default)]
+struct Frame {
+    sort: Option<SortSpec>,
+    sort_changed: bool,
+    headers: Vec<(f32, f32)>,
+    rows: Vec<(f32, f32)>,
+    clicked_row: Option<usize>,
+    widths: Vec<f32>,
+}
+
+fn table_frame(harness: &mut Harness, default_sort: Option<SortSpec>) -> Frame {
+    let mut frame = Frame::default();
+    let columns = columns();
+    harness.frame(|ui| {
+        window(ui, || {
+            let header_y = ui.get_cursor_screen_pos().1;
+            let table = ui.table(im_str!("files"), &columns);
+            let table = match default_sort {
+                Some(sort) => table.default_sort(sort),
+                None => table,
+            };
+            table.build(|body| {
+                frame.sort = body.sort_spec();
+                frame.sort_changed = body.sort_spec_changed();
+                frame.headers = header_positions(ui, header_y, columns.len());
+                frame.widths = (0..columns.len())
+                    .map(|column| ui.get_column_width(column as i32))
+                    .collect();
+                for row in 0..3 {
+                    frame.rows.push(ui.get_cursor_screen_pos());
+                    if body.row(im_str!("Row"), false, |column| {
+                        ui.text(im_str!("{}", column));
+                    }) {
+                        frame.clicked_row = Some(row);
+                    }
+                }
+            });
+        })
+    });
+    frame
+}
+
+#[test]
+fn default_sort_is_reported_when_the_table_appears() {
+    let mut harness = Harness::new(300.0, 200.0);
+    let default_sort = SortSpec {
+        column: 1,
+        direction: SortDirection::Descending,
+    };
+    let frame = table_frame(&mut harness, Some(default_sort));
+    assert_eq!(frame.sort, Some(default_sort));
+    assert!(frame.sort_changed);
+    let frame = table_frame(&mut harness, Some(default_sort));
+    assert_eq!(frame.sort, Some(default_sort));
+    assert!(!frame.sort_changed);
+}
+
+#[test]
+fn initial_column_widths_are_applied() {
+    let mut harness = Harness::new(300.0, 200.0);
+    let frame = table_frame(&mut harness, None);
+    assert_eq!(frame.widths[0], 80.0);
+    assert_eq!(frame.widths[1], 60.0);
+    assert!(frame.widths[2] > 60.0);
+}
+
+#[test]
+fn clicking_a_header_toggles_the_sort_direction() {
+    let mut harness = Harness::new(300.0, 200.0);
+    let frame = table_frame(&mut harness, None);
+    assert_eq!(frame.sort, None);
+    let headers = frame.headers;
+
+    // Returns the sort spec after clicking a header, and whether it changed
+    let sort_after_click = |harness: &mut Harness, column: usize| {
+        click(harness, headers[column]);
+        table_frame(harness, None);
+        release(harness);
+        let frame = table_frame(harness, None);
+        let frame_after = table_frame(harness, None);
+        assert!(!frame_after.sort_changed);
+        assert_eq!(frame_after.sort, frame.sort);
+        (frame.sort, frame.sort_changed)
+    };
+    let spec = |column, direction| Some(SortSpec { column, direction });
+    assert_eq!(
+        sort_after_click(&mut harness, 1),
+        (spec(1, SortDirection::Ascending), true)
+    );
+    assert_eq!(
+        sort_after_click(&mut harness, 1),
+        (spec(1, SortDirection::Descending), true)
+    );
+    assert_eq!(
+        sort_after_click(&mut harness, 1),
+        (spec(1, SortDirection::Ascending), true)
+    );
+    assert_eq!(
+        sort_after_click(&mut harness, 0),
+        (spec(0, SortDirection::Ascending), true)
+    );
+    // Columns that are not sortable have a plain text header
+    assert_eq!(
+        sort_after_click(&mut harness, 2),
+        (spec(0, SortDirection::Ascending), false)
+    );
+}
+
+#[test]
+fn rows_are_clicked_in_any_column() {
+    let mut harness = Harness::new(300.0, 200.0);
+    let frame = table_frame(&mut harness, None);
+    let x = frame.headers[2].0;
+    let y = frame.rows[1].1;
+
+    click(&mut harness, (x, y));
+    assert_eq!(table_frame(&mut harness, None).clicked_row, None);
+    release(&mut harness);
+    assert_eq!(table_frame(&mut harness, None).clicked_row, Some(1));
+}
+
+#[test]
+#[should_panic(expected = "at least one column")]
+fn tables_need_a_column() {
+    let mut harness = Harness::new(300.0, 200.0);
+    harness.frame(|ui| {
+        ui.table(im_str!("empty"), &[]).build(|_| ());
+    });
+}
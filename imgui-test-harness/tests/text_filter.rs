@@ -0,0 +1,59 @@
+use imgui::*;
+use imgui_test_harness::Harness;
+use std::borrow::Cow;
+use std::cell::RefCell;
+
+const ITEMS: [&str; 3] = ["Alpha", "Beta", "Gamma"];
+
+fn window<F: FnOnce()>(ui: &Ui, f: F) {
+    ui.window(im_str!("Window"))
+        .position((0.0, 0.0), ImGuiCond::Always)
+        .size((200.0, 200.0), ImGuiCond::Always)
+        .build(f);
+}
+
+#[test]
+fn list_box_skips_filtered_items() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let filter = TextFilter::with_filter("-Beta");
+    let positions = RefCell::new(Vec::new());
+    let mut current = 0;
+    harness.frame(|ui| {
+        window(ui, || {
+            ui.list_box_header(im_str!("List"))
+                .filter(&filter)
+                .build_simple(&ITEMS, &mut current, |item| {
+                    positions.borrow_mut().push(ui.get_cursor_screen_pos());
+                    Cow::Owned(ImString::new(*item))
+                });
+        })
+    });
+    let positions = positions.into_inner();
+    // Gamma takes the place of Beta
+    assert!(positions[0].1 < positions[1].1);
+    assert_eq!(positions[1], positions[2]);
+}
+
+#[test]
+fn tree_nodes_are_filtered_by_label() {
+    let mut harness = Harness::new(200.0, 200.0);
+    let filter = TextFilter::with_filter("Gamma,Beta");
+    let mut built = Vec::new();
+    harness.frame(|ui| {
+        window(ui, || {
+            for item in &ITEMS {
+                let id = ImString::new(format!("{}##node", item));
+                ui.tree_node(&id)
+                    .filter(&filter)
+                    .opened(true, ImGuiCond::Always)
+                    .build(|| built.push(*item));
+            }
+            ui.tree_node(im_str!("id"))
+                .label(im_str!("Beta label"))
+                .filter(&filter)
+                .opened(true, ImGuiCond::Always)
+                .build(|| built.push("label"));
+        })
+    });
+    assert_eq!(built, ["Beta", "Gamma", "label"]);
+}
@@ -0,0 +1,202 @@
+use imgui::*;
+use imgui_test_harness::Harness;
+use std::f32;
+
+fn window_size<'ui, 'p>(window: Window<'ui, 'p>, ui: &Ui) -> (f32, f32) {
+    let mut size = (0.0, 0.0);
+    window
+        .position((0.0, 0.0), ImGuiCond::Always)
+        .build(|| size = ui.get_window_size());
+    size
+}
+
+#[test]
+fn size_constraints_clamp_the_size() {
+    let mut harness = Harness::new(400.0, 400.0);
+    harness.frame(|ui| {
+        let window = ui
+            .window(im_str!("Max"))
+            .size((300.0, 300.0), ImGuiCond::Always)
+            .size_constraints((0.0, 0.0), (150.0, 120.0));
+        assert_eq!(window_size(window, ui), (150.0, 120.0));
+
+        let window = ui
+            .window(im_str!("Min"))
+            .size((50.0, 50.0), ImGuiCond::Always)
+            .size_constraints((100.0, -1.0), (f32::MAX, f32::MAX));
+        assert_eq!(window_size(window, ui), (100.0, 50.0));
+    });
+}
+
+#[test]
+fn size_callback_adjusts_the_desired_size() {
+    let mut harness = Harness::new(400.0, 400.0);
+    let mut calls = Vec::new();
+    harness.frame(|ui| {
+        let window = ui
+            .window(im_str!("Square"))
+            .size((100.0, 50.0), ImGuiCond::Always)
+            .size_constraints_callback((0.0, 0.0), (f32::MAX, f32::MAX), |data| {
+                calls.push(data.desired_size);
+                let side = data.desired_size.0.max(data.desired_size.1);
+                data.desired_size = (side, side);
+            });
+        assert_eq!(window_size(window, ui), (100.0, 100.0));
+    });
+    assert_eq!(calls.last(), Some(&(100.0, 50.0)));
+}
+
+#[test]
+fn content_size_sets_the_scroll_range() {
+    let mut harness = Harness::new(400.0, 400.0);
+    let mut scroll_max = (0.0, 0.0);
+    harness.frames(2, |ui| {
+        ui.window(im_str!("Content"))
+            .position((0.0, 0.0), ImGuiCond::Always)
+            .size((100.0, 100.0), ImGuiCond::Always)
+            .horizontal_scrollbar(true)
+            .content_size((500.0, 300.0))
+            .build(|| scroll_max = (ui.get_scroll_max_x(), ui.get_scroll_max_y()));
+    });
+    assert!(scroll_max.0 > 400.0, "{:?}", scroll_max);
+    assert!(scroll_max.1 > 200.0, "{:?}", scroll_max);
+}
+
+#[test]
+fn collapsed_windows_are_not_built() {
+    let mut harness = Harness::new(400.0, 400.0);
+    let mut built = Vec::new();
+    for frame in 0..3 {
+        harness.frame(|ui| {
+            ui.window(im_str!("Collapsed"))
+                .collapsed(frame == 1, ImGuiCond::Always)
+                .build(|| built.push(frame));
+        });
+    }
+    assert_eq!(built, [0, 2]);
+}
+
+#[test]
+fn focused_window_is_brought_to_the_front() {
+    let mut harness = Harness::new(400.0, 400.0);
+    let mut focused = (false, false);
+    for frame in 0..4 {
+        harness.frame(|ui| {
+            ui.window(im_str!("A"))
+                .position((0.0, 0.0), ImGuiCond::Always)
+                .focused(frame == 2)
+                .build(|| focused.0 = ui.is_window_focused());
+            ui.window(im_str!("B"))
+                .position((0.0, 0.0), ImGuiCond::Always)
+                .build(|| focused.1 = ui.is_window_focused());
+        });
+        // The most recently created window has the focus until another one is focused. In
+        // the first frame, A is focused until B is created
+        let expected = match frame {
+            0 => (true, true),
+            1 => (false, true),
+            _ => (true, false),
+        };
+        assert_eq!(focused, expected, "frame {}", frame);
+    }
+}
+
+#[test]
+fn bg_alpha_overrides_the_style() {
+    let mut harness = Harness::new(100.0, 100.0);
+    harness.set_clear_color([255, 0, 0, 255]);
+    let image = harness
+        .render(|ui| {
+            ui.window(im_str!("Transparent"))
+                .position((0.0, 0.0), ImGuiCond::Always)
+                .size((100.0, 100.0), ImGuiCond::Always)
+                .bg_alpha(0.0)
+                .build(|| ());
+        })
+        .unwrap();
+    // Below the title bar, only the clear color is visible
+    assert_eq!(image.get_pixel(50, 50), [255, 0, 0, 255]);
+}
+
+#[derive(Debug, Default)]
+struct WindowState {
+    built: bool,
+    appearing: bool,
+    pos: (f32, f32),
+    size: (f32, f32),
+    focused: bool,
+}
+
+fn query_window<F: FnOnce()>(ui: &Ui, name: &ImStr, f: F) -> WindowState {
+    let mut state = WindowState::default();
+    ui.window(name).build(|| {
+        state = WindowState {
+            built: true,
+            appearing: ui.is_window_appearing(),
+            pos: ui.get_window_pos(),
+            size: (ui.get_window_width(), ui.get_window_height()),
+            focused: ui.is_window_focused(),
+        };
+        assert_eq!(state.size, ui.get_window_size());
+        assert!(!ui.is_window_collapsed());
+        f();
+    });
+    state
+}
+
+#[test]
+fn current_window_is_controlled() {
+    let mut harness = Harness::new(400.0, 400.0);
+    harness.frame(|ui| {
+        let state = query_window(ui, im_str!("A"), || {
+            ui.set_window_pos((10.0, 20.0), ImGuiCond::Always);
+            ui.set_window_size((150.0, 100.0), ImGuiCond::Always);
+        });
+        assert!(state.appearing);
+    });
+    // Windows are hidden in their first frame, so they are appearing in the next one too
+    harness.frame(|ui| {
+        assert!(query_window(ui, im_str!("A"), || ()).appearing);
+    });
+    let mut line_height = 0.0;
+    harness.frame(|ui| {
+        let state = query_window(ui, im_str!("A"), || {
+            line_height = ui.get_text_line_height_with_spacing();
+            ui.set_window_font_scale(2.0);
+            assert!(ui.get_text_line_height_with_spacing() > line_height * 1.5);
+            ui.set_window_collapsed(true, ImGuiCond::Always);
+        });
+        assert!(!state.appearing);
+        assert_eq!(state.pos, (10.0, 20.0));
+        assert_eq!(state.size, (150.0, 100.0));
+    });
+    harness.frame(|ui| {
+        let state = query_window(ui, im_str!("A"), || ());
+        assert!(!state.built);
+    });
+}
+
+#[test]
+fn windows_are_controlled_by_name() {
+    let mut harness = Harness::new(400.0, 400.0);
+    harness.frame(|ui| {
+        query_window(ui, im_str!("A"), || ());
+        query_window(ui, im_str!("B"), || ());
+    });
+    harness.frame(|ui| {
+        let a = query_window(ui, im_str!("A"), || ());
+        assert!(!a.focused);
+        query_window(ui, im_str!("B"), || ());
+        ui.set_window_pos_by_name(im_str!("A"), (30.0, 40.0), ImGuiCond::Always);
+        ui.set_window_size_by_name(im_str!("A"), (120.0, 90.0), ImGuiCond::Always);
+        ui.set_window_focus_by_name(im_str!("A"));
+        ui.set_window_collapsed_by_name(im_str!("B"), true, ImGuiCond::Always);
+    });
+    harness.frame(|ui| {
+        let a = query_window(ui, im_str!("A"), || ());
+        assert_eq!(a.pos, (30.0, 40.0));
+        assert_eq!(a.size, (120.0, 90.0));
+        assert!(a.focused);
+        assert!(!query_window(ui, im_str!("B"), || ()).built);
+    });
+}
@@ -0,0 +1,51 @@
+use sys;
+
+/// Keeps a scrolling region at the bottom while content is appended, unless the user has
+/// scrolled up, see [`Ui::auto_scroll`].
+#[derive(Clone, Debug)]
+pub struct AutoScroll {
+    at_bottom: bool,
+    scroll_to_bottom: bool,
+    content_height: f32,
+}
+
+impl AutoScroll {
+    pub fn new() -> AutoScroll {
+        AutoScroll {
+            at_bottom: true,
+            scroll_to_bottom: false,
+            content_height: 0.0,
+        }
+    }
+    /// Returns true if the view was scrolled to the bottom before the last content was
+    /// submitted.
+    pub fn is_at_bottom(&self) -> bool {
+        self.at_bottom
+    }
+    /// Scrolls to the bottom the next time the content is submitted, even if the user has
+    /// scrolled up.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_to_bottom = true;
+    }
+    pub(crate) fn build<F: FnOnce()>(&mut self, f: F) {
+        // The scroll position and range are those of the previous frame, i.e. before new
+        // content is submitted
+        self.at_bottom = unsafe { sys::igGetScrollY() >= sys::igGetScrollMaxY() };
+        f();
+        // Only scroll when content is appended: the scroll target would override the mouse
+        // wheel in the next frame, so the user couldn't scroll up
+        let content_height = unsafe { sys::igGetCursorPosY() };
+        let appended = content_height > self.content_height;
+        self.content_height = content_height;
+        if (self.at_bottom && appended) || self.scroll_to_bottom {
+            unsafe { sys::igSetScrollHereY(1.0) };
+            self.scroll_to_bottom = false;
+        }
+    }
+}
+
+impl Default for AutoScroll {
+    fn default() -> AutoScroll {
+        AutoScroll::new()
+    }
+}
@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 use sys;
 
-use super::{ImGuiWindowFlags, ImStr, ImVec2, Ui};
+use super::{AutoScroll, ImGuiWindowFlags, ImStr, ImVec2, Ui};
 
 #[must_use]
 pub struct ChildFrame<'ui, 'p> {
@@ -9,6 +9,9 @@ pub struct ChildFrame<'ui, 'p> {
     size: ImVec2,
     border: bool,
     flags: ImGuiWindowFlags,
+    scroll_x: Option<f32>,
+    scroll_y: Option<f32>,
+    auto_scroll: Option<&'p mut AutoScroll>,
     _phantom: PhantomData<&'ui Ui<'ui>>,
 }
 
@@ -19,6 +22,9 @@ impl<'ui, 'p> ChildFrame<'ui, 'p> {
             size: size.into(),
             border: false,
             flags: ImGuiWindowFlags::empty(),
+            scroll_x: None,
+            scroll_y: None,
+            auto_scroll: None,
             _phantom: PhantomData,
         }
     }
@@ -96,11 +102,38 @@ impl<'ui, 'p> ChildFrame<'ui, 'p> {
             .set(ImGuiWindowFlags::AlwaysUseWindowPadding, value);
         self
     }
+    /// Set the horizontal scroll position.
+    #[inline]
+    pub fn scroll_x(mut self, value: f32) -> Self {
+        self.scroll_x = Some(value);
+        self
+    }
+    /// Set the vertical scroll position.
+    #[inline]
+    pub fn scroll_y(mut self, value: f32) -> Self {
+        self.scroll_y = Some(value);
+        self
+    }
+    /// Keep the contents scrolled to the bottom unless the user has scrolled up.
+    #[inline]
+    pub fn auto_scroll(mut self, state: &'p mut AutoScroll) -> Self {
+        self.auto_scroll = Some(state);
+        self
+    }
     pub fn build<F: FnOnce()>(self, f: F) {
         let render_child_frame =
             unsafe { sys::igBeginChild(self.name.as_ptr(), self.size, self.border, self.flags) };
         if render_child_frame {
-            f();
+            if let Some(scroll_x) = self.scroll_x {
+                unsafe { sys::igSetScrollX(scroll_x) };
+            }
+            if let Some(scroll_y) = self.scroll_y {
+                unsafe { sys::igSetScrollY(scroll_y) };
+            }
+            match self.auto_scroll {
+                Some(state) => state.build(f),
+                None => f(),
+            }
         }
         unsafe { sys::igEndChild() };
     }
@@ -0,0 +1,86 @@
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+use std::sync::{Arc, Mutex};
+
+use super::{abort_on_panic, ImStr, ImString};
+
+/// Clipboard used by dear imgui for copy and paste, see [`ImGui::set_clipboard_backend`].
+///
+/// Backends are owned by `ImGui`, which can be sent to other threads.
+pub trait ClipboardBackend: Send {
+    /// Returns the current clipboard contents, or `None` if it is empty or not text.
+    fn get(&mut self) -> Option<ImString>;
+    /// Replaces the clipboard contents.
+    fn set(&mut self, value: &ImStr);
+}
+
+/// Clipboard that only exists in memory, e.g. for tests.
+///
+/// Clones share the same contents, so a clone can be kept to inspect the clipboard after the
+/// backend has been given to `ImGui`.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryClipboard {
+    contents: Arc<Mutex<Option<ImString>>>,
+}
+
+impl MemoryClipboard {
+    pub fn new() -> MemoryClipboard {
+        MemoryClipboard::default()
+    }
+    pub fn contents(&self) -> Option<ImString> {
+        self.contents.lock().unwrap().clone()
+    }
+    pub fn set_contents(&self, value: Option<ImString>) {
+        *self.contents.lock().unwrap() = value;
+    }
+}
+
+impl ClipboardBackend for MemoryClipboard {
+    fn get(&mut self) -> Option<ImString> {
+        self.contents()
+    }
+    fn set(&mut self, value: &ImStr) {
+        self.set_contents(Some(ImString::new(value.to_str())));
+    }
+}
+
+/// Passed to the C callbacks as `ImGuiIO::clipboard_user_data`.
+pub(crate) struct ClipboardContext {
+    backend: Box<dyn ClipboardBackend>,
+    // The string returned to dear imgui must stay alive until the next call
+    last_value: ImString,
+}
+
+impl ClipboardContext {
+    pub(crate) fn new(backend: Box<dyn ClipboardBackend>) -> ClipboardContext {
+        ClipboardContext {
+            backend,
+            last_value: ImString::default(),
+        }
+    }
+}
+
+pub(crate) extern "C" fn get_clipboard_text(user_data: *mut c_void) -> *const c_char {
+    abort_on_panic(|| {
+        let ctx = unsafe { &mut *(user_data as *mut ClipboardContext) };
+        match ctx.backend.get() {
+            Some(value) => {
+                ctx.last_value = value;
+                ctx.last_value.as_ptr()
+            }
+            None => ptr::null(),
+        }
+    })
+}
+
+pub(crate) extern "C" fn set_clipboard_text(user_data: *mut c_void, text: *const c_char) {
+    abort_on_panic(|| {
+        let ctx = unsafe { &mut *(user_data as *mut ClipboardContext) };
+        // Dear imgui always passes UTF-8 text
+        let text = unsafe {
+            ImStr::from_utf8_with_nul_unchecked(CStr::from_ptr(text).to_bytes_with_nul())
+        };
+        ctx.backend.set(text);
+    })
+}
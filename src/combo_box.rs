@@ -0,0 +1,201 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+use std::ptr;
+use sys;
+use sys::ImGuiComboFlags;
+
+use super::{ImGuiSelectableFlags, ImStr, ImVec2, TextFilter, Ui};
+
+/// Created by call to [`Ui::combo_box`].
+#[must_use]
+pub struct ComboBox<'ui, 'p> {
+    label: &'p ImStr,
+    preview_value: Option<&'p ImStr>,
+    flags: ImGuiComboFlags,
+    filter: Option<&'p TextFilter>,
+    _phantom: PhantomData<&'ui Ui<'ui>>,
+}
+
+impl<'ui, 'p> ComboBox<'ui, 'p> {
+    pub fn new(_: &Ui<'ui>, label: &'p ImStr) -> Self {
+        ComboBox {
+            label,
+            preview_value: None,
+            flags: ImGuiComboFlags::empty(),
+            filter: None,
+            _phantom: PhantomData,
+        }
+    }
+    /// Set the text displayed in the closed combo box.
+    ///
+    /// `build_simple` displays the label of the selected item by default.
+    #[inline]
+    pub fn preview_value(mut self, preview_value: &'p ImStr) -> Self {
+        self.preview_value = Some(preview_value);
+        self
+    }
+    #[inline]
+    pub fn flags(mut self, flags: ImGuiComboFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+    #[inline]
+    pub fn popup_align_left(mut self, value: bool) -> Self {
+        self.flags.set(ImGuiComboFlags::PopupAlignLeft, value);
+        self
+    }
+    /// Show at most ~4 items in the popup.
+    #[inline]
+    pub fn height_small(self) -> Self {
+        self.set_height(ImGuiComboFlags::HeightSmall)
+    }
+    /// Show at most ~8 items in the popup (default).
+    #[inline]
+    pub fn height_regular(self) -> Self {
+        self.set_height(ImGuiComboFlags::HeightRegular)
+    }
+    /// Show at most ~20 items in the popup.
+    #[inline]
+    pub fn height_large(self) -> Self {
+        self.set_height(ImGuiComboFlags::HeightLarge)
+    }
+    /// Show as many items in the popup as fit on the screen.
+    #[inline]
+    pub fn height_largest(self) -> Self {
+        self.set_height(ImGuiComboFlags::HeightLargest)
+    }
+    #[inline]
+    pub fn arrow_button(mut self, value: bool) -> Self {
+        self.flags.set(ImGuiComboFlags::NoArrowButton, !value);
+        self
+    }
+    /// Show only the arrow button (default: false).
+    #[inline]
+    pub fn no_preview(mut self, value: bool) -> Self {
+        self.flags.set(ImGuiComboFlags::NoPreview, value);
+        self
+    }
+    /// Only show the items whose label passes `filter` in `build_simple`.
+    #[inline]
+    pub fn filter(mut self, filter: &'p TextFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+    fn set_height(mut self, height: ImGuiComboFlags) -> Self {
+        self.flags.remove(ImGuiComboFlags::HeightMask);
+        self.flags.insert(height);
+        self
+    }
+    /// Calls `f` to submit the contents of the popup while the combo box is open.
+    ///
+    /// Returns true if the combo box is open.
+    pub fn build<F: FnOnce()>(self, f: F) -> bool {
+        let preview_value = self
+            .preview_value
+            .map(|p| p.as_ptr())
+            .unwrap_or(ptr::null());
+        let open = unsafe { sys::igBeginCombo(self.label.as_ptr(), preview_value, self.flags) };
+        if open {
+            f();
+            unsafe { sys::igEndCombo() };
+        }
+        open
+    }
+    /// Submits a selectable for each item, labeled by `label_fn`.
+    ///
+    /// Returns true if the selection was changed.
+    pub fn build_simple<T, L>(self, items: &[T], current_item: &mut usize, label_fn: L) -> bool
+    where
+        L: Fn(&T) -> Cow<ImStr>,
+    {
+        let preview = items.get(*current_item).map(&label_fn);
+        let preview_value = match (self.preview_value, preview.as_ref()) {
+            (Some(value), _) => value.as_ptr(),
+            (None, Some(value)) => value.as_ptr(),
+            (None, None) => ptr::null(),
+        };
+        let mut changed = false;
+        if unsafe { sys::igBeginCombo(self.label.as_ptr(), preview_value, self.flags) } {
+            changed = select_items(items, current_item, &label_fn, self.filter);
+            unsafe { sys::igEndCombo() };
+        }
+        changed
+    }
+    /// Like `build_simple`, but the selection is the item itself.
+    pub fn build_simple_value<T, L>(self, items: &[T], current_item: &mut T, label_fn: L) -> bool
+    where
+        T: Clone + PartialEq,
+        L: Fn(&T) -> Cow<ImStr>,
+    {
+        let mut index = position(items, current_item);
+        let changed = self.build_simple(items, &mut index, label_fn);
+        if changed {
+            *current_item = items[index].clone();
+        }
+        changed
+    }
+}
+
+/// Returns the index of `value` in `items`, or an index past the end if it is not found.
+pub(crate) fn position<T: PartialEq>(items: &[T], value: &T) -> usize {
+    items
+        .iter()
+        .position(|item| item == value)
+        .unwrap_or(items.len())
+}
+
+/// Submits a selectable for each item that passes the filter and updates `current_item` when
+/// one is clicked.
+pub(crate) fn select_items<T, L>(
+    items: &[T],
+    current_item: &mut usize,
+    label_fn: &L,
+    filter: Option<&TextFilter>,
+) -> bool
+where
+    L: Fn(&T) -> Cow<ImStr>,
+{
+    let mut changed = false;
+    for (index, item) in items.iter().enumerate() {
+        let selected = index == *current_item;
+        let label = label_fn(item);
+        if let Some(filter) = filter {
+            if !filter.pass_filter(label.to_str()) {
+                continue;
+            }
+        }
+        // Items may have the same label
+        unsafe {
+            sys::igPushIDInt(index as c_int);
+            if sys::igSelectable(
+                label.as_ptr(),
+                selected,
+                ImGuiSelectableFlags::empty(),
+                ImVec2::new(0.0, 0.0),
+            ) {
+                *current_item = index;
+                changed = true;
+            }
+            if selected {
+                sys::igSetItemDefaultFocus();
+            }
+            sys::igPopID();
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::position;
+
+    #[test]
+    fn position_of_missing_item_is_past_the_end() {
+        let items = ["a", "b", "b"];
+        assert_eq!(position(&items, &"a"), 0);
+        assert_eq!(position(&items, &"b"), 1);
+        assert_eq!(position(&items, &"c"), 3);
+        assert_eq!(position(&[] as &[&str], &"a"), 0);
+    }
+}
@@ -0,0 +1,156 @@
+use std::cell::{Cell, RefCell};
+use std::ptr;
+use std::sync::{Arc, Mutex, MutexGuard, Once, PoisonError};
+
+use sys;
+
+use super::ImFontAtlas;
+
+/// Dear imgui keeps the current context in a process-wide global, so a thread holds this lock
+/// while any context is current.
+static mut CONTEXT_LOCK: *const Mutex<()> = ptr::null();
+// `Once::new` is not a `const fn` before Rust 1.32
+#[allow(deprecated)]
+static CONTEXT_LOCK_INIT: Once = std::sync::ONCE_INIT;
+
+fn lock_context() -> MutexGuard<'static, ()> {
+    let lock = unsafe {
+        CONTEXT_LOCK_INIT.call_once(|| CONTEXT_LOCK = Box::into_raw(Box::new(Mutex::new(()))));
+        &*CONTEXT_LOCK
+    };
+    // The lock doesn't protect any data, so a poisoned lock is still usable
+    lock.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+struct HeldLock {
+    lock: Option<MutexGuard<'static, ()>>,
+    guards: usize,
+}
+
+thread_local! {
+    /// The context lock if this thread holds it, and how many guards are alive.
+    static HELD_LOCK: RefCell<HeldLock> = RefCell::new(HeldLock {
+        lock: None,
+        guards: 0,
+    });
+    /// Whether a frame is in progress on this thread.
+    static IN_FRAME: Cell<bool> = Cell::new(false);
+}
+
+/// Makes a context current for as long as the guard is alive.
+///
+/// The first guard on a thread takes the context lock, so other threads wait until this thread
+/// no longer uses any context. Guards can be nested on one thread, also for different contexts:
+/// dropping a guard makes the previously current context current again.
+pub(crate) struct ContextGuard {
+    previous: *mut sys::ImGuiContext,
+    frame: bool,
+}
+
+impl ContextGuard {
+    pub(crate) fn acquire(ctx: *mut sys::ImGuiContext) -> ContextGuard {
+        HELD_LOCK.with(|held| {
+            let mut held = held.borrow_mut();
+            if held.guards == 0 {
+                held.lock = Some(lock_context());
+            }
+            held.guards += 1;
+        });
+        let previous = unsafe { sys::igGetCurrentContext() };
+        unsafe { sys::igSetCurrentContext(ctx) };
+        ContextGuard {
+            previous,
+            frame: false,
+        }
+    }
+    /// Like `acquire`, for the guard held by a frame.
+    ///
+    /// `Ui` functions assume that the context of their frame is current, so a thread can only
+    /// have one frame in progress at a time.
+    pub(crate) fn acquire_for_frame(ctx: *mut sys::ImGuiContext) -> ContextGuard {
+        assert!(
+            !IN_FRAME.with(|in_frame| in_frame.replace(true)),
+            "A frame of another imgui context is already in progress on this thread"
+        );
+        let mut guard = ContextGuard::acquire(ctx);
+        guard.frame = true;
+        guard
+    }
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        unsafe { sys::igSetCurrentContext(self.previous) };
+        if self.frame {
+            IN_FRAME.with(|in_frame| in_frame.set(false));
+        }
+        HELD_LOCK.with(|held| {
+            let mut held = held.borrow_mut();
+            held.guards -= 1;
+            if held.guards == 0 {
+                held.lock = None;
+            }
+        });
+    }
+}
+
+/// Pointers to an owned dear imgui context.
+pub(crate) struct RawContext {
+    pub(crate) ctx: *mut sys::ImGuiContext,
+    // The IO state is a field of the context, so it can be used without making the context
+    // current
+    pub(crate) io: *mut sys::ImGuiIO,
+}
+
+// Dear imgui contexts are not tied to a thread. They are only used while current, which
+// requires the context lock
+unsafe impl Send for RawContext {}
+
+/// A font atlas that can be shared between multiple contexts.
+///
+/// # Example
+/// ```rust,no_run
+/// # use imgui::*;
+/// let atlas = SharedFontAtlas::create();
+/// atlas.lock().unwrap().fonts().add_default_font();
+/// let main_window = ImGui::init_with_shared_font_atlas(atlas.clone());
+/// let tool_window = ImGui::init_with_shared_font_atlas(atlas);
+/// ```
+pub struct SharedFontAtlas {
+    // cimgui doesn't expose the ImFontAtlas constructor, so the atlas is owned by a context
+    // that is never used for frames
+    owner: *mut sys::ImGuiContext,
+    atlas: *mut sys::ImFontAtlas,
+}
+
+impl SharedFontAtlas {
+    pub fn create() -> Arc<Mutex<SharedFontAtlas>> {
+        let owner = {
+            let _guard = ContextGuard::acquire(ptr::null_mut());
+            unsafe { sys::igCreateContext(ptr::null_mut()) }
+        };
+        let atlas = {
+            let _guard = ContextGuard::acquire(owner);
+            unsafe { (*sys::igGetIO()).fonts }
+        };
+        Arc::new(Mutex::new(SharedFontAtlas { owner, atlas }))
+    }
+    /// Fonts should be added before any context using the atlas starts a frame.
+    pub fn fonts(&mut self) -> ImFontAtlas {
+        unsafe { ImFontAtlas::from_ptr(self.atlas, ContextGuard::acquire(self.owner)) }
+    }
+    pub(crate) fn raw(&self) -> *mut sys::ImFontAtlas {
+        self.atlas
+    }
+}
+
+// The atlas is only accessed while holding the context lock: through `ImFontAtlas`, or during
+// frames of the contexts using it
+unsafe impl Send for SharedFontAtlas {}
+
+impl Drop for SharedFontAtlas {
+    fn drop(&mut self) {
+        let _guard = ContextGuard::acquire(self.owner);
+        unsafe { sys::igDestroyContext(self.owner) };
+    }
+}
@@ -0,0 +1,95 @@
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+use std::ptr;
+use sys;
+
+use super::{ImMouseButton, ImStr, Ui};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ContextMenuKind {
+    Item,
+    Window { also_over_items: bool },
+    Void,
+}
+
+/// Created by calls to [`Ui::context_menu_item`], [`Ui::context_menu_window`] and
+/// [`Ui::context_menu_void`].
+#[must_use]
+pub struct ContextMenu<'ui, 'p> {
+    kind: ContextMenuKind,
+    str_id: Option<&'p ImStr>,
+    mouse_button: ImMouseButton,
+    _phantom: PhantomData<&'ui Ui<'ui>>,
+}
+
+impl<'ui, 'p> ContextMenu<'ui, 'p> {
+    fn new(kind: ContextMenuKind) -> Self {
+        ContextMenu {
+            kind,
+            str_id: None,
+            mouse_button: ImMouseButton::Right,
+            _phantom: PhantomData,
+        }
+    }
+    pub fn item(_: &Ui<'ui>) -> Self {
+        ContextMenu::new(ContextMenuKind::Item)
+    }
+    pub fn window(_: &Ui<'ui>) -> Self {
+        ContextMenu::new(ContextMenuKind::Window {
+            also_over_items: true,
+        })
+    }
+    pub fn void(_: &Ui<'ui>) -> Self {
+        ContextMenu::new(ContextMenuKind::Void)
+    }
+    /// Set the id of the popup, e.g. to open it with [`Ui::open_popup`].
+    ///
+    /// By default, item context menus use the id of the previous item, which must have one
+    /// (e.g. not text).
+    #[inline]
+    pub fn str_id(mut self, str_id: &'p ImStr) -> Self {
+        self.str_id = Some(str_id);
+        self
+    }
+    /// Set the mouse button that opens the menu (default: right).
+    #[inline]
+    pub fn mouse_button(mut self, button: ImMouseButton) -> Self {
+        self.mouse_button = button;
+        self
+    }
+    /// Also open window context menus when clicking over items (default: true).
+    ///
+    /// Set it to false if items have their own context menus, which would be replaced by the
+    /// window context menu otherwise.
+    #[inline]
+    pub fn also_over_items(mut self, value: bool) -> Self {
+        if let ContextMenuKind::Window {
+            ref mut also_over_items,
+        } = self.kind
+        {
+            *also_over_items = value;
+        }
+        self
+    }
+    /// Calls `f` to submit the contents of the menu while it is open.
+    ///
+    /// Returns true if the menu is open.
+    pub fn build<F: FnOnce()>(self, f: F) -> bool {
+        let str_id = self.str_id.map(|id| id.as_ptr()).unwrap_or(ptr::null());
+        let button = self.mouse_button as c_int;
+        let open = unsafe {
+            match self.kind {
+                ContextMenuKind::Item => sys::igBeginPopupContextItem(str_id, button),
+                ContextMenuKind::Window { also_over_items } => {
+                    sys::igBeginPopupContextWindow(str_id, button, also_over_items)
+                }
+                ContextMenuKind::Void => sys::igBeginPopupContextVoid(str_id, button),
+            }
+        };
+        if open {
+            f();
+            unsafe { sys::igEndPopup() };
+        }
+        open
+    }
+}
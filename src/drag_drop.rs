@@ -0,0 +1,295 @@
+use std::any::TypeId;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+
+use sys;
+use sys::{ImGuiCond, ImGuiDragDropFlags, ImGuiPayload};
+
+use super::{ImStr, Ui};
+
+/// Maximum length of a payload type name, as defined by dear imgui.
+const MAX_PAYLOAD_NAME_LEN: usize = 32;
+
+/// What is actually stored in the dear imgui payload buffer.
+///
+/// The `TypeId` guards against two call sites using the same payload name for
+/// different Rust types.
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct TypedPayload<T> {
+    type_id: TypeId,
+    data: T,
+}
+
+/// Returned when a payload with the requested name exists, but was created
+/// with a different Rust type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PayloadIsWrongType;
+
+impl fmt::Display for PayloadIsWrongType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Drag and drop payload has a different type")
+    }
+}
+
+/// A typed drag and drop payload.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DragDropPayload<T> {
+    /// Data sent by the drag and drop source
+    pub data: T,
+    /// The mouse is hovering the target item, but the button has not been released yet.
+    pub preview: bool,
+    /// The mouse button was released over the target item.
+    pub delivery: bool,
+}
+
+unsafe fn read_payload<T: Copy + 'static>(
+    payload: *const ImGuiPayload,
+) -> Result<DragDropPayload<T>, PayloadIsWrongType> {
+    let payload_mut = payload as *mut ImGuiPayload;
+    let data = read_typed_payload((*payload).data, (*payload).data_size)?;
+    Ok(DragDropPayload {
+        data,
+        preview: sys::ImGuiPayload_IsPreview(payload_mut),
+        delivery: sys::ImGuiPayload_IsDelivery(payload_mut),
+    })
+}
+
+unsafe fn read_typed_payload<T: Copy + 'static>(
+    data: *const c_void,
+    size: c_int,
+) -> Result<T, PayloadIsWrongType> {
+    if size < 0 || size as usize != mem::size_of::<TypedPayload<T>>() {
+        return Err(PayloadIsWrongType);
+    }
+    // The payload buffer is a byte vector owned by dear imgui, so no alignment is guaranteed.
+    // The whole payload is only read once the type is known, since the bytes may not be a
+    // valid `T`. `TypeId` is the first field of the `repr(C)` struct, so it is at offset 0
+    let type_id = ptr::read_unaligned(data as *const TypeId);
+    if type_id != TypeId::of::<T>() {
+        return Err(PayloadIsWrongType);
+    }
+    let typed = ptr::read_unaligned(data as *const TypedPayload<T>);
+    Ok(typed.data)
+}
+
+fn check_name(name: &ImStr) {
+    assert!(
+        name.to_str().len() <= MAX_PAYLOAD_NAME_LEN,
+        "Drag and drop payload names are limited to {} bytes",
+        MAX_PAYLOAD_NAME_LEN
+    );
+}
+
+/// Created by call to [`Ui::drag_drop_source`].
+///
+/// The source applies to the previously submitted item.
+#[must_use]
+pub struct DragDropSource<'ui, 'p> {
+    name: &'p ImStr,
+    flags: ImGuiDragDropFlags,
+    cond: ImGuiCond,
+    _phantom: PhantomData<&'ui Ui<'ui>>,
+}
+
+impl<'ui, 'p> DragDropSource<'ui, 'p> {
+    pub fn new(_: &Ui<'ui>, name: &'p ImStr) -> Self {
+        check_name(name);
+        DragDropSource {
+            name,
+            flags: ImGuiDragDropFlags::empty(),
+            cond: ImGuiCond::Always,
+            _phantom: PhantomData,
+        }
+    }
+    #[inline]
+    pub fn flags(mut self, flags: ImGuiDragDropFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+    /// Set when the payload is submitted (default: every frame).
+    #[inline]
+    pub fn condition(mut self, cond: ImGuiCond) -> Self {
+        self.cond = cond;
+        self
+    }
+    /// Show the preview tooltip while dragging (default: true).
+    #[inline]
+    pub fn preview_tooltip(mut self, value: bool) -> Self {
+        self.flags
+            .set(ImGuiDragDropFlags::SourceNoPreviewTooltip, !value);
+        self
+    }
+    /// Keep reporting the source item as hovered while dragging (default: false).
+    #[inline]
+    pub fn hover_while_dragging(mut self, value: bool) -> Self {
+        self.flags
+            .set(ImGuiDragDropFlags::SourceNoDisableHover, value);
+        self
+    }
+    /// Open tree nodes and collapsing headers by holding the payload over them (default: true).
+    #[inline]
+    pub fn hold_to_open_others(mut self, value: bool) -> Self {
+        self.flags
+            .set(ImGuiDragDropFlags::SourceNoHoldToOpenOthers, !value);
+        self
+    }
+    /// Allow items without an id (e.g. text or images) to be used as the source.
+    #[inline]
+    pub fn allow_null_id(mut self, value: bool) -> Self {
+        self.flags.set(ImGuiDragDropFlags::SourceAllowNullID, value);
+        self
+    }
+    /// Expire the payload if the source is no longer submitted (default: false).
+    #[inline]
+    pub fn auto_expire_payload(mut self, value: bool) -> Self {
+        self.flags
+            .set(ImGuiDragDropFlags::SourceAutoExpirePayload, value);
+        self
+    }
+    /// Starts dragging `payload` if the item is being dragged, and calls `f` to
+    /// draw the preview tooltip contents.
+    ///
+    /// Returns true if the item is being dragged.
+    pub fn build<T, F>(self, payload: T, f: F) -> bool
+    where
+        T: Copy + 'static,
+        F: FnOnce(),
+    {
+        let render = unsafe { sys::igBeginDragDropSource(self.flags) };
+        if render {
+            let typed = TypedPayload {
+                type_id: TypeId::of::<T>(),
+                data: payload,
+            };
+            unsafe {
+                sys::igSetDragDropPayload(
+                    self.name.as_ptr(),
+                    &typed as *const TypedPayload<T> as *const c_void,
+                    mem::size_of::<TypedPayload<T>>(),
+                    self.cond,
+                );
+            }
+            f();
+            unsafe { sys::igEndDragDropSource() };
+        }
+        render
+    }
+}
+
+/// Passed to the closure given to [`Ui::drag_drop_target`].
+pub struct DragDropTarget<'ui> {
+    _phantom: PhantomData<&'ui Ui<'ui>>,
+}
+
+impl<'ui> DragDropTarget<'ui> {
+    pub(crate) fn new(_: &Ui<'ui>) -> Self {
+        DragDropTarget {
+            _phantom: PhantomData,
+        }
+    }
+    /// Accepts a payload with the given name.
+    ///
+    /// Returns `None` if no such payload is hovering the target. Unless
+    /// `ImGuiDragDropFlags::AcceptBeforeDelivery` is set, the payload is only
+    /// returned once the mouse button is released.
+    pub fn accept_payload<T: Copy + 'static>(
+        &self,
+        name: &ImStr,
+        flags: ImGuiDragDropFlags,
+    ) -> Option<Result<DragDropPayload<T>, PayloadIsWrongType>> {
+        check_name(name);
+        unsafe {
+            let payload = sys::igAcceptDragDropPayload(name.as_ptr(), flags);
+            if payload.is_null() {
+                None
+            } else {
+                Some(read_payload(payload))
+            }
+        }
+    }
+}
+
+/// Returns the payload currently being dragged, if it has the given name.
+pub(crate) fn current_payload<T: Copy + 'static>(
+    name: &ImStr,
+) -> Option<Result<DragDropPayload<T>, PayloadIsWrongType>> {
+    check_name(name);
+    unsafe {
+        let payload = sys::igGetDragDropPayload();
+        if payload.is_null()
+            || !sys::ImGuiPayload_IsDataType(payload as *mut ImGuiPayload, name.as_ptr())
+        {
+            None
+        } else {
+            Some(read_payload(payload))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImString;
+
+    /// Returns the payload bytes preceded by one byte, so that the payload
+    /// is unaligned like the buffer owned by dear imgui can be.
+    fn payload_bytes<T: Copy + 'static>(data: T) -> Vec<u8> {
+        let typed = TypedPayload {
+            type_id: TypeId::of::<T>(),
+            data,
+        };
+        let size = mem::size_of::<TypedPayload<T>>();
+        let mut bytes = vec![0u8; size + 1];
+        unsafe {
+            ptr::copy_nonoverlapping(
+                &typed as *const TypedPayload<T> as *const u8,
+                bytes[1..].as_mut_ptr(),
+                size,
+            );
+        }
+        bytes
+    }
+
+    unsafe fn read<T: Copy + 'static>(bytes: &[u8]) -> Result<T, PayloadIsWrongType> {
+        let payload = &bytes[1..];
+        read_typed_payload(payload.as_ptr() as *const c_void, payload.len() as c_int)
+    }
+
+    #[test]
+    fn reads_payload_of_the_same_type() {
+        let bytes = payload_bytes((42u8, 1.5f64));
+        assert_eq!(unsafe { read::<(u8, f64)>(&bytes) }, Ok((42, 1.5)));
+    }
+
+    #[test]
+    fn rejects_payload_of_a_different_size() {
+        let bytes = payload_bytes(42u32);
+        assert_eq!(unsafe { read::<u64>(&bytes) }, Err(PayloadIsWrongType));
+        assert_eq!(
+            unsafe { read::<u32>(&bytes[..bytes.len() - 1]) },
+            Err(PayloadIsWrongType)
+        );
+    }
+
+    #[test]
+    fn rejects_payload_of_a_different_type_with_the_same_size() {
+        let bytes = payload_bytes(42u32);
+        assert_eq!(unsafe { read::<i32>(&bytes) }, Err(PayloadIsWrongType));
+        assert_eq!(unsafe { read::<f32>(&bytes) }, Err(PayloadIsWrongType));
+    }
+
+    #[test]
+    fn accepts_names_up_to_32_bytes() {
+        check_name(&ImString::new("a".repeat(MAX_PAYLOAD_NAME_LEN)));
+    }
+
+    #[test]
+    #[should_panic(expected = "limited to 32 bytes")]
+    fn rejects_names_longer_than_32_bytes() {
+        check_name(&ImString::new("a".repeat(MAX_PAYLOAD_NAME_LEN + 1)));
+    }
+}
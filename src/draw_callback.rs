@@ -0,0 +1,119 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::os::raw::c_void;
+use std::ptr;
+use sys;
+use sys::{ImDrawCmd, ImDrawList};
+
+use super::{ContextState, ImTexture, ImVec4};
+
+/// A command of a [`DrawList`](super::DrawList), see [`DrawList::commands`].
+///
+/// [`DrawList::commands`]: super::DrawList::commands
+#[derive(Copy, Clone)]
+pub enum DrawCmd<'a> {
+    /// Draw the next `count` indices of the index buffer with a texture, clipped to
+    /// `clip_rect`.
+    Elements {
+        count: usize,
+        clip_rect: ImVec4,
+        texture_id: ImTexture,
+    },
+    /// Restore the render state of the renderer, which a previous callback may have changed.
+    ResetRenderState,
+    /// Call `callback` with the render target.
+    Callback {
+        callback: &'a DrawCallback,
+        clip_rect: ImVec4,
+    },
+}
+
+impl<'a> DrawCmd<'a> {
+    pub(crate) fn from_raw(cmd: &'a ImDrawCmd) -> Option<DrawCmd<'a>> {
+        match cmd.user_callback {
+            None => Some(DrawCmd::Elements {
+                count: cmd.elem_count as usize,
+                clip_rect: cmd.clip_rect,
+                texture_id: cmd.texture_id.into(),
+            }),
+            Some(callback) if callback as usize == marker as MarkerFn as usize => {
+                match cmd.user_callback_data as usize {
+                    0 => Some(DrawCmd::ResetRenderState),
+                    index => Some(DrawCmd::Callback {
+                        callback: unsafe { registered_callback(index - 1) },
+                        clip_rect: cmd.clip_rect,
+                    }),
+                }
+            }
+            // Callbacks added through the C API can't be given the render target
+            Some(_) => None,
+        }
+    }
+}
+
+type BoxedCallback = Box<dyn FnMut(&mut dyn Any, ImVec4) -> bool + Send>;
+
+/// A closure added with [`WindowDrawList::add_callback`], called by the renderer.
+///
+/// [`WindowDrawList::add_callback`]: super::WindowDrawList::add_callback
+pub struct DrawCallback {
+    f: RefCell<BoxedCallback>,
+}
+
+impl DrawCallback {
+    /// Calls the closure with the render target and the clip rectangle of the command, in
+    /// framebuffer coordinates.
+    ///
+    /// Returns false without calling it if the closure expects another type of render target.
+    pub fn call(&self, target: &mut dyn Any, clip_rect: ImVec4) -> bool {
+        let f = &mut **self.f.borrow_mut();
+        f(target, clip_rect)
+    }
+}
+
+type MarkerFn = extern "C" fn(*const ImDrawList, *const ImDrawCmd);
+
+// Identifies the commands added by this module. Its data is the index of the callback in
+// `ContextState::draw_callbacks` plus one, or null for the reset sentinel. Renderers written
+// in C call it, so it must not do anything
+extern "C" fn marker(_: *const ImDrawList, _: *const ImDrawCmd) {}
+
+unsafe fn context_state<'a>() -> &'a ContextState {
+    &*((*sys::igGetIO()).user_data as *const ContextState)
+}
+
+// The callbacks are only added while the frame is built and cleared when the next frame
+// starts, so they aren't moved while the draw data is rendered
+unsafe fn registered_callback<'a>(index: usize) -> &'a DrawCallback {
+    &(&*context_state().draw_callbacks.as_ptr())[index]
+}
+
+pub(crate) fn add_callback<T, F>(draw_list: *mut ImDrawList, mut f: F)
+where
+    T: Any,
+    F: FnMut(&mut T, ImVec4) + Send + 'static,
+{
+    let callback = DrawCallback {
+        f: RefCell::new(Box::new(
+            move |target: &mut dyn Any, clip_rect| match target.downcast_mut::<T>() {
+                Some(target) => {
+                    f(target, clip_rect);
+                    true
+                }
+                None => false,
+            },
+        )),
+    };
+    unsafe {
+        // The callback must live until the frame is rendered, so the context keeps it until
+        // the next frame
+        let mut callbacks = context_state().draw_callbacks.borrow_mut();
+        callbacks.push(callback);
+        let data = callbacks.len() as *mut c_void;
+        sys::ImDrawList_AddCallback(draw_list, Some(marker), data);
+    }
+}
+
+pub(crate) fn add_reset_render_state(draw_list: *mut ImDrawList) {
+    unsafe { sys::ImDrawList_AddCallback(draw_list, Some(marker), ptr::null_mut()) };
+}
@@ -5,6 +5,8 @@ use std::os::raw::{c_float, c_int, c_void};
 use std::ptr;
 use sys;
 
+use super::context::ContextGuard;
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 enum FontGlyphRangeData {
     ChineseSimplifiedCommon,
@@ -293,15 +295,23 @@ impl<'a> ImFont<'a> {
 }
 
 /// A handle to imgui's font manager.
+///
+/// The atlas may be shared with other contexts, so the handle keeps the context that owns it
+/// current.
 #[repr(C)]
 pub struct ImFontAtlas<'a> {
     atlas: *mut sys::ImFontAtlas,
+    _context: ContextGuard,
     _phantom: PhantomData<&'a mut sys::ImFontAtlas>,
 }
 impl<'a> ImFontAtlas<'a> {
-    pub(crate) unsafe fn from_ptr(atlas: *mut sys::ImFontAtlas) -> ImFontAtlas<'a> {
+    pub(crate) unsafe fn from_ptr(
+        atlas: *mut sys::ImFontAtlas,
+        context: ContextGuard,
+    ) -> ImFontAtlas<'a> {
         ImFontAtlas {
             atlas,
+            _context: context,
             _phantom: PhantomData,
         }
     }
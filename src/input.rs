@@ -1,9 +1,13 @@
+use std::char;
 use std::marker::PhantomData;
-use std::os::raw::{c_int, c_void};
+use std::ops::Range;
+use std::os::raw::{c_char, c_int, c_void};
 use std::ptr;
+use std::slice;
+use std::str;
 use sys;
 
-use super::{ImGuiInputTextFlags, ImStr, ImString, Ui};
+use super::{ImGuiInputTextFlags, ImGuiKey, ImStr, ImString, Ui};
 
 macro_rules! impl_text_flags {
     ($InputType:ident) => {
@@ -133,24 +137,233 @@ macro_rules! impl_step_params {
     }
 }
 
-extern "C" fn resize_callback(data: *mut sys::ImGuiInputTextCallbackData) -> c_int {
+/// Direction of a history request, see [`InputTextCallbackHandler::on_history`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HistoryDirection {
+    Up,
+    Down,
+}
+
+/// Receives callbacks from [`InputText`] and [`InputTextMultiline`].
+///
+/// A method is only called if the matching flag is enabled on the widget
+/// (`callback_completion`, `callback_history`, `callback_char_filter` or
+/// `callback_always`). All methods have a default implementation that does nothing.
+///
+/// # Example
+/// ```rust,no_run
+/// # use imgui::*;
+/// struct History(Vec<String>);
+///
+/// impl InputTextCallbackHandler for History {
+///     fn on_history(&mut self, direction: HistoryDirection, mut data: TextCallbackData) {
+///         if direction == HistoryDirection::Up {
+///             if let Some(entry) = self.0.last() {
+///                 data.replace_text(entry);
+///             }
+///         }
+///     }
+/// }
+///
+/// # let mut imgui = ImGui::init();
+/// # let ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+/// let mut history = History(vec!["help".to_owned()]);
+/// let mut command = ImString::with_capacity(16);
+/// ui.input_text(im_str!("Command"), &mut command)
+///     .callback_history(true)
+///     .resize_buffer(true)
+///     .callback_handler(&mut history)
+///     .build();
+/// ```
+pub trait InputTextCallbackHandler {
+    /// Called when the user presses Tab.
+    fn on_completion(&mut self, _data: TextCallbackData) {}
+    /// Called when the user presses the up or down arrow key.
+    fn on_history(&mut self, _direction: HistoryDirection, _data: TextCallbackData) {}
+    /// Called every frame while the widget is active.
+    fn on_always(&mut self, _data: TextCallbackData) {}
+    /// Called for every typed character.
+    ///
+    /// Return `None` to discard the character, or a (possibly different) character to insert.
+    fn char_filter(&mut self, c: char) -> Option<char> {
+        Some(c)
+    }
+}
+
+/// Access to the text being edited, passed to [`InputTextCallbackHandler`].
+///
+/// Positions and lengths are in bytes.
+pub struct TextCallbackData<'a> {
+    data: *mut sys::ImGuiInputTextCallbackData,
+    _phantom: PhantomData<&'a mut sys::ImGuiInputTextCallbackData>,
+}
+
+impl<'a> TextCallbackData<'a> {
+    fn raw(&self) -> &sys::ImGuiInputTextCallbackData {
+        unsafe { &*self.data }
+    }
+    fn raw_mut(&mut self) -> &mut sys::ImGuiInputTextCallbackData {
+        unsafe { &mut *self.data }
+    }
+    /// Flags of the widget that triggered the callback
+    pub fn flags(&self) -> ImGuiInputTextFlags {
+        self.raw().flags
+    }
+    /// Key that triggered the callback (Tab, UpArrow or DownArrow)
+    pub fn event_key(&self) -> ImGuiKey {
+        self.raw().event_key
+    }
+    /// Current contents of the edit buffer
+    pub fn text(&self) -> &str {
+        let data = self.raw();
+        unsafe {
+            let bytes = slice::from_raw_parts(data.buf as *const u8, data.buf_text_len as usize);
+            str::from_utf8_unchecked(bytes)
+        }
+    }
+    pub fn cursor_pos(&self) -> usize {
+        self.raw().cursor_pos as usize
+    }
+    pub fn set_cursor_pos(&mut self, pos: usize) {
+        self.assert_boundary(pos);
+        self.raw_mut().cursor_pos = pos as c_int;
+    }
+    /// Selected byte range. The range is empty if nothing is selected.
+    pub fn selection(&self) -> Range<usize> {
+        let data = self.raw();
+        let (a, b) = (data.selection_start as usize, data.selection_end as usize);
+        if a <= b {
+            a..b
+        } else {
+            b..a
+        }
+    }
+    pub fn set_selection(&mut self, range: Range<usize>) {
+        self.assert_boundary(range.start);
+        self.assert_boundary(range.end);
+        let data = self.raw_mut();
+        data.selection_start = range.start as c_int;
+        data.selection_end = range.end as c_int;
+    }
+    pub fn has_selection(&self) -> bool {
+        unsafe { sys::ImGuiInputTextCallbackData_HasSelection(self.data) }
+    }
+    pub fn select_all(&mut self) {
+        let len = self.text().len();
+        self.set_selection(0..len);
+    }
+    pub fn clear_selection(&mut self) {
+        let pos = self.cursor_pos();
+        self.set_selection(pos..pos);
+    }
+    /// Inserts text at the given byte position.
+    ///
+    /// If the text doesn't fit and the buffer is not resizable, nothing is inserted.
+    pub fn insert_chars(&mut self, pos: usize, text: &str) {
+        self.assert_boundary(pos);
+        unsafe {
+            let start = text.as_ptr() as *const c_char;
+            let end = start.add(text.len());
+            sys::ImGuiInputTextCallbackData_InsertChars(self.data, pos as c_int, start, end);
+        }
+    }
+    /// Appends text at the end of the buffer.
+    pub fn push_str(&mut self, text: &str) {
+        let len = self.text().len();
+        self.insert_chars(len, text);
+    }
+    /// Deletes `byte_count` bytes starting at the given byte position.
+    pub fn delete_chars(&mut self, pos: usize, byte_count: usize) {
+        self.assert_boundary(pos);
+        self.assert_boundary(pos + byte_count);
+        unsafe {
+            sys::ImGuiInputTextCallbackData_DeleteChars(
+                self.data,
+                pos as c_int,
+                byte_count as c_int,
+            );
+        }
+    }
+    /// Deletes the whole buffer contents.
+    pub fn clear(&mut self) {
+        let len = self.text().len();
+        self.delete_chars(0, len);
+    }
+    /// Replaces the whole buffer contents.
+    pub fn replace_text(&mut self, text: &str) {
+        self.clear();
+        self.insert_chars(0, text);
+    }
+    fn assert_boundary(&self, pos: usize) {
+        assert!(
+            self.text().is_char_boundary(pos),
+            "Position {} is out of bounds or not on a char boundary",
+            pos
+        );
+    }
+}
+
+struct CallbackUserData<'a> {
+    buf: *mut ImString,
+    handler: Option<&'a mut dyn InputTextCallbackHandler>,
+}
+
+fn callback_flags() -> ImGuiInputTextFlags {
+    ImGuiInputTextFlags::CallbackCompletion
+        | ImGuiInputTextFlags::CallbackHistory
+        | ImGuiInputTextFlags::CallbackAlways
+        | ImGuiInputTextFlags::CallbackCharFilter
+        | ImGuiInputTextFlags::CallbackResize
+}
+
+extern "C" fn input_text_callback(data: *mut sys::ImGuiInputTextCallbackData) -> c_int {
     unsafe {
-        if (*data).event_flag == ImGuiInputTextFlags::CallbackResize {
-            if let Some(buffer) = ((*data).user_data as *mut ImString).as_mut() {
-                let requested_size = (*data).buf_size as usize;
-                if requested_size > buffer.capacity_with_nul() {
-                    // Refresh the buffer's length to take into account changes made by dear imgui.
-                    buffer.refresh_len();
-                    // Add 1 to include the null terminator, so that reserve sees the right length.
-                    // After we're done we'll call refresh_len, so this won't be visible to the user.
-                    buffer.0.set_len(buffer.0.len() + 1);
-                    buffer.reserve(requested_size - buffer.0.len());
-                    (*data).buf = buffer.as_mut_ptr();
-                    (*data).buf_dirty = true;
-                }
+        let user_data = &mut *((*data).user_data as *mut CallbackUserData);
+        let event_flag = (*data).event_flag;
+        if event_flag == ImGuiInputTextFlags::CallbackResize {
+            let buffer = &mut *user_data.buf;
+            let requested_size = (*data).buf_size as usize;
+            if requested_size > buffer.capacity_with_nul() {
+                // Refresh the buffer's length to take into account changes made by dear imgui.
+                buffer.refresh_len();
+                // Add 1 to include the null terminator, so that reserve sees the right length.
+                // After we're done we'll call refresh_len, so this won't be visible to the user.
+                buffer.0.set_len(buffer.0.len() + 1);
+                buffer.reserve(requested_size - buffer.0.len());
+                (*data).buf = buffer.as_mut_ptr();
+                (*data).buf_dirty = true;
+            }
+            return 0;
+        }
+        let handler = match user_data.handler {
+            Some(ref mut handler) => handler,
+            None => return 0,
+        };
+        let callback_data = TextCallbackData {
+            data,
+            _phantom: PhantomData,
+        };
+        if event_flag == ImGuiInputTextFlags::CallbackCompletion {
+            handler.on_completion(callback_data);
+        } else if event_flag == ImGuiInputTextFlags::CallbackHistory {
+            match (*data).event_key {
+                ImGuiKey::UpArrow => handler.on_history(HistoryDirection::Up, callback_data),
+                ImGuiKey::DownArrow => handler.on_history(HistoryDirection::Down, callback_data),
+                _ => (),
+            }
+        } else if event_flag == ImGuiInputTextFlags::CallbackAlways {
+            handler.on_always(callback_data);
+        } else if event_flag == ImGuiInputTextFlags::CallbackCharFilter {
+            let filtered = char::from_u32(u32::from((*data).event_char))
+                .and_then(|c| handler.char_filter(c))
+                .filter(|&c| (c as u32) <= 0xFFFF);
+            match filtered {
+                // Dear imgui only supports characters in the Basic Multilingual Plane
+                Some(c) => (*data).event_char = c as sys::ImWchar,
+                None => return 1,
             }
         }
-        return 0
+        0
     }
 }
 
@@ -159,6 +372,7 @@ pub struct InputText<'ui, 'p> {
     label: &'p ImStr,
     buf: &'p mut ImString,
     flags: ImGuiInputTextFlags,
+    handler: Option<&'p mut dyn InputTextCallbackHandler>,
     _phantom: PhantomData<&'ui Ui<'ui>>,
 }
 
@@ -168,28 +382,43 @@ impl<'ui, 'p> InputText<'ui, 'p> {
             label,
             buf,
             flags: ImGuiInputTextFlags::empty(),
+            handler: None,
             _phantom: PhantomData,
         }
     }
 
     impl_text_flags!(InputText);
 
-    // TODO: boxed closure...?
-    // pub fn callback(self) -> Self { }
+    /// Set the handler for the callbacks enabled with the `callback_*` flags.
+    ///
+    /// The buffer is grown automatically if `resize_buffer` is enabled, with or without a
+    /// handler.
+    #[inline]
+    pub fn callback_handler(mut self, handler: &'p mut dyn InputTextCallbackHandler) -> Self {
+        self.handler = Some(handler);
+        self
+    }
 
     pub fn build(self) -> bool {
         let (ptr, capacity) = (self.buf.as_mut_ptr(), self.buf.capacity_with_nul());
+        let mut user_data = CallbackUserData {
+            buf: self.buf,
+            handler: self.handler,
+        };
         let (callback, data): (sys::ImGuiInputTextCallback, _) = {
-            if self.flags.contains(ImGuiInputTextFlags::CallbackResize) {
-                (Some(resize_callback), self.buf as *mut _ as *mut c_void)
-            } else { 
-                (None, ptr::null_mut()) 
+            if self.flags.intersects(callback_flags()) {
+                (
+                    Some(input_text_callback),
+                    &mut user_data as *mut _ as *mut c_void,
+                )
+            } else {
+                (None, ptr::null_mut())
             }
         };
-        
+
         unsafe {
             let result = sys::igInputText(self.label.as_ptr(), ptr, capacity, self.flags, callback, data);
-            self.buf.refresh_len();
+            (*user_data.buf).refresh_len();
             result
         }
     }
@@ -200,6 +429,7 @@ pub struct InputTextMultiline<'ui, 'p> {
     label: &'p ImStr,
     buf: &'p mut ImString,
     flags: ImGuiInputTextFlags,
+    handler: Option<&'p mut dyn InputTextCallbackHandler>,
     size: sys::ImVec2,
     _phantom: PhantomData<&'ui Ui<'ui>>,
 }
@@ -210,6 +440,7 @@ impl<'ui, 'p> InputTextMultiline<'ui, 'p> {
             label,
             buf,
             flags: ImGuiInputTextFlags::empty(),
+            handler: None,
             size,
             _phantom: PhantomData,
         }
@@ -217,22 +448,36 @@ impl<'ui, 'p> InputTextMultiline<'ui, 'p> {
 
     impl_text_flags!(InputText);
 
-    // TODO: boxed closure...?
-    // pub fn callback(self) -> Self { }
+    /// Set the handler for the callbacks enabled with the `callback_*` flags.
+    ///
+    /// The buffer is grown automatically if `resize_buffer` is enabled, with or without a
+    /// handler.
+    #[inline]
+    pub fn callback_handler(mut self, handler: &'p mut dyn InputTextCallbackHandler) -> Self {
+        self.handler = Some(handler);
+        self
+    }
 
     pub fn build(self) -> bool {
         let (ptr, capacity) = (self.buf.as_mut_ptr(), self.buf.capacity_with_nul());
+        let mut user_data = CallbackUserData {
+            buf: self.buf,
+            handler: self.handler,
+        };
         let (callback, data): (sys::ImGuiInputTextCallback, _) = {
-            if self.flags.contains(ImGuiInputTextFlags::CallbackResize) {
-                (Some(resize_callback), self.buf as *mut _ as *mut c_void)
-            } else { 
-                (None, ptr::null_mut()) 
+            if self.flags.intersects(callback_flags()) {
+                (
+                    Some(input_text_callback),
+                    &mut user_data as *mut _ as *mut c_void,
+                )
+            } else {
+                (None, ptr::null_mut())
             }
         };
-        
+
         unsafe {
             let result = sys::igInputTextMultiline(self.label.as_ptr(), ptr, capacity, self.size, self.flags, callback, data);
-            self.buf.refresh_len();
+            (*user_data.buf).refresh_len();
             result
         }
     }
@@ -389,3 +634,82 @@ macro_rules! impl_input_intn {
 impl_input_intn!(InputInt2, 2, igInputInt2);
 impl_input_intn!(InputInt3, 3, igInputInt3);
 impl_input_intn!(InputInt4, 4, igInputInt4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem;
+
+    /// Runs `f` on callback data for a buffer of `buf_size` bytes containing
+    /// `text`, and returns the resulting text.
+    fn edit<F: FnOnce(&mut TextCallbackData)>(text: &str, buf_size: usize, f: F) -> String {
+        let mut buf = vec![0u8; buf_size];
+        buf[..text.len()].copy_from_slice(text.as_bytes());
+        let mut raw: sys::ImGuiInputTextCallbackData = unsafe { mem::zeroed() };
+        raw.buf = buf.as_mut_ptr() as *mut c_char;
+        raw.buf_size = buf_size as c_int;
+        raw.buf_text_len = text.len() as c_int;
+        let mut data = TextCallbackData {
+            data: &mut raw,
+            _phantom: PhantomData,
+        };
+        f(&mut data);
+        data.text().to_owned()
+    }
+
+    #[test]
+    fn inserts_and_deletes_at_char_boundaries() {
+        let text = edit("héllo", 16, |data| {
+            data.insert_chars(3, "y");
+            data.push_str("!");
+            data.delete_chars(0, 1);
+        });
+        assert_eq!(text, "éyllo!");
+    }
+
+    #[test]
+    fn replaces_text() {
+        let text = edit("hello", 16, |data| data.replace_text("wörld"));
+        assert_eq!(text, "wörld");
+    }
+
+    #[test]
+    fn does_not_insert_past_a_fixed_buffer() {
+        let text = edit("hello", 8, |data| data.push_str("world"));
+        assert_eq!(text, "hello");
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn selection_is_ordered() {
+        edit("hello", 16, |data| {
+            data.set_selection(4..1);
+            assert_eq!(data.selection(), 1..4);
+            assert!(data.has_selection());
+            data.set_cursor_pos(2);
+            data.clear_selection();
+            assert_eq!(data.selection(), 2..2);
+            assert!(!data.has_selection());
+            data.select_all();
+            assert_eq!(data.selection(), 0..5);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "not on a char boundary")]
+    fn cursor_pos_inside_a_char_panics() {
+        edit("é", 16, |data| data.set_cursor_pos(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn delete_past_the_end_panics() {
+        edit("hello", 16, |data| data.delete_chars(3, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "not on a char boundary")]
+    fn insert_inside_a_char_panics() {
+        edit("é", 16, |data| data.insert_chars(1, "x"));
+    }
+}
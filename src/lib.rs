@@ -1,68 +1,118 @@
 pub extern crate imgui_sys as sys;
 
+use std::cell::{RefCell, UnsafeCell};
 use std::ffi::CStr;
+use std::fs;
 use std::mem;
 use std::os::raw::{c_char, c_float, c_int, c_uchar, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::process;
 use std::ptr;
 use std::slice;
 use std::str;
+use std::sync::{Arc, Mutex};
 use std::thread;
 use sys::ImGuiStyleVar;
 
+use self::clipboard::ClipboardContext;
+use self::context::{ContextGuard, RawContext};
+use self::settings::{KeyValueHandler, SettingsHandlers};
+
+pub use self::auto_scroll::AutoScroll;
 pub use self::child_frame::ChildFrame;
+pub use self::clipboard::{ClipboardBackend, MemoryClipboard};
 pub use self::color_editors::{
     ColorButton, ColorEdit, ColorEditMode, ColorFormat, ColorPicker, ColorPickerMode, ColorPreview,
     EditableColor,
 };
+pub use self::combo_box::ComboBox;
+pub use self::context::SharedFontAtlas;
+pub use self::context_menu::ContextMenu;
 pub use self::drag::{
     DragFloat, DragFloat2, DragFloat3, DragFloat4, DragFloatRange2, DragInt, DragInt2, DragInt3,
     DragInt4, DragIntRange2,
 };
+pub use self::drag_drop::{DragDropPayload, DragDropSource, DragDropTarget, PayloadIsWrongType};
+pub use self::draw_callback::{DrawCallback, DrawCmd};
 pub use self::fonts::{FontGlyphRange, ImFont, ImFontAtlas, ImFontConfig};
 pub use self::image::{ImTexture, Image, ImageButton, Textures};
 pub use self::input::{
-    InputFloat, InputFloat2, InputFloat3, InputFloat4, InputInt, InputInt2, InputInt3, InputInt4,
-    InputText, InputTextMultiline,
+    HistoryDirection, InputFloat, InputFloat2, InputFloat3, InputFloat4, InputInt, InputInt2,
+    InputInt3, InputInt4, InputText, InputTextCallbackHandler, InputTextMultiline,
+    TextCallbackData,
 };
+pub use self::list_box::ListBoxHeader;
+pub use self::list_clipper::ListClipper;
+pub use self::logging::LogTarget;
 pub use self::menus::{Menu, MenuItem};
 pub use self::plothistogram::PlotHistogram;
 pub use self::plotlines::PlotLines;
 pub use self::popup_modal::PopupModal;
 pub use self::progressbar::ProgressBar;
+pub use self::scalar::{DataType, DragScalar, InputScalar, ScalarType, SliderScalar};
+pub use self::settings::{KeyValueSettings, SettingsHandler, SettingsWriter};
 pub use self::sliders::{
     SliderFloat, SliderFloat2, SliderFloat3, SliderFloat4, SliderInt, SliderInt2, SliderInt3,
     SliderInt4,
 };
+pub use self::storage::{StateStorage, Storage};
 pub use self::string::{ImStr, ImString};
-pub use self::style::StyleVar;
+pub use self::style::{StyleParseError, StyleParseErrorKind, StylePreset, StyleVar};
 pub use self::sys::{
-    ImDrawIdx, ImDrawVert, ImGuiCol, ImGuiColorEditFlags, ImGuiCond, ImGuiFocusedFlags,
-    ImGuiHoveredFlags, ImGuiInputTextFlags, ImGuiKey, ImGuiMouseCursor, ImGuiSelectableFlags,
-    ImGuiStyle, ImGuiTreeNodeFlags, ImGuiWindowFlags, ImVec2, ImVec4,
+    ImDrawIdx, ImDrawVert, ImGuiCol, ImGuiColorEditFlags, ImGuiComboFlags, ImGuiCond,
+    ImGuiDragDropFlags, ImGuiFocusedFlags, ImGuiHoveredFlags, ImGuiID, ImGuiInputTextFlags,
+    ImGuiKey, ImGuiMouseCursor, ImGuiSelectableFlags, ImGuiStyle, ImGuiTreeNodeFlags,
+    ImGuiWindowFlags, ImVec2, ImVec4,
 };
+pub use self::table::{SortDirection, SortSpec, Table, TableBody, TableColumn};
+pub use self::text_filter::TextFilter;
 pub use self::trees::{CollapsingHeader, TreeNode};
-pub use self::window::Window;
-pub use self::window_draw_list::{ChannelsSplit, ImColor, WindowDrawList};
+pub use self::window::{SizeCallbackData, Window};
+pub use self::window_draw_list::{ChannelsSplit, ImColor, PrimWriter, WindowDrawList};
 
+mod auto_scroll;
 mod child_frame;
+mod clipboard;
 mod color_editors;
+mod combo_box;
+mod context;
+mod context_menu;
 mod drag;
+mod drag_drop;
+mod draw_callback;
 mod fonts;
 mod image;
 mod input;
+mod list_box;
+mod list_clipper;
+mod logging;
 mod menus;
 mod plothistogram;
 mod plotlines;
 mod popup_modal;
 mod progressbar;
+mod scalar;
+mod settings;
 mod sliders;
+mod storage;
 mod string;
 mod style;
+mod table;
+mod text_filter;
 mod trees;
 mod window;
 mod window_draw_list;
 
 pub struct ImGui {
+    context: RawContext,
+    state: Box<ContextState>,
+    shared_font_atlas: Option<Arc<Mutex<SharedFontAtlas>>>,
+    clipboard: Option<Box<ClipboardContext>>,
+    settings_handlers: SettingsHandlers,
+    settings_loaded: bool,
+    // Counts down from `ini_saving_rate` after the settings of a handler changed
+    settings_dirty_timer: f32,
     // We need to keep ownership of the ImStr values to ensure the *const char pointer
     // lives long enough in case the ImStr contains a Cow::Owned
     ini_filename: Option<ImString>,
@@ -129,35 +179,134 @@ impl FrameSize {
     }
 }
 
+/// State kept per context, reachable from the context through `ImGuiIO::user_data`.
+struct ContextState {
+    current_ui: UnsafeCell<Option<Ui<'static>>>,
+    draw_callbacks: RefCell<Vec<DrawCallback>>,
+}
+
+// `current_ui` is only set while a frame borrows the `ImGui`, so it never moves to another thread.
+// The draw callbacks are `Send`
+unsafe impl Send for ContextState {}
+
 impl ImGui {
+    /// Creates a new context.
+    ///
+    /// Multiple contexts can exist at the same time, even on different threads. Because dear
+    /// imgui keeps the current context in a global variable, only one thread uses a context at
+    /// a time: frames on different threads are serialized.
     pub fn init() -> ImGui {
-        unsafe {
-            sys::igCreateContext(ptr::null_mut());
-        }
-        ImGui {
+        ImGui::create(None)
+    }
+    /// Creates a new context that uses a font atlas shared with other contexts.
+    pub fn init_with_shared_font_atlas(shared_font_atlas: Arc<Mutex<SharedFontAtlas>>) -> ImGui {
+        ImGui::create(Some(shared_font_atlas))
+    }
+    fn create(shared_font_atlas: Option<Arc<Mutex<SharedFontAtlas>>>) -> ImGui {
+        let atlas = shared_font_atlas
+            .as_ref()
+            .map(|atlas| atlas.lock().unwrap().raw())
+            .unwrap_or(ptr::null_mut());
+        let context = {
+            let _guard = ContextGuard::acquire(ptr::null_mut());
+            unsafe { sys::igCreateContext(atlas) }
+        };
+        let io = {
+            let _guard = ContextGuard::acquire(context);
+            unsafe { sys::igGetIO() }
+        };
+        let mut imgui = ImGui {
+            context: RawContext { ctx: context, io },
+            state: Box::new(ContextState {
+                current_ui: UnsafeCell::new(None),
+                draw_callbacks: RefCell::new(Vec::new()),
+            }),
+            shared_font_atlas,
+            clipboard: None,
+            settings_handlers: SettingsHandlers::default(),
+            settings_loaded: false,
+            settings_dirty_timer: 0.0,
             ini_filename: None,
             log_filename: None,
-        }
+        };
+        let state = &*imgui.state as *const ContextState as *mut c_void;
+        let io = imgui.io_mut();
+        io.user_data = state;
+        imgui
+    }
+    fn activate(&self) -> ContextGuard {
+        ContextGuard::acquire(self.context.ctx)
     }
     fn io(&self) -> &sys::ImGuiIO {
-        unsafe { &*sys::igGetIO() }
+        unsafe { &*self.context.io }
     }
     fn io_mut(&mut self) -> &mut sys::ImGuiIO {
-        unsafe { &mut *sys::igGetIO() }
+        unsafe { &mut *self.context.io }
     }
     pub fn style(&self) -> &ImGuiStyle {
+        let _guard = self.activate();
         unsafe { &*sys::igGetStyle() }
     }
     pub fn style_mut(&mut self) -> &mut ImGuiStyle {
+        let _guard = self.activate();
         unsafe { &mut *sys::igGetStyle() }
     }
+    /// Replaces the style colors with a built-in color scheme.
+    pub fn apply_style_preset(&mut self, preset: StylePreset) {
+        preset.apply(self.style_mut());
+    }
+    /// Scales all sizes in the style, e.g. by the hidpi factor.
+    ///
+    /// Should be applied once to an unscaled style, because values are rounded.
+    pub fn scale_style_sizes(&mut self, scale: f32) {
+        unsafe { sys::ImGuiStyle_ScaleAllSizes(self.style_mut(), scale) }
+    }
+    pub fn style_color(&self, color: ImGuiCol) -> ImVec4 {
+        self.style().colors[color as usize]
+    }
+    pub fn set_style_color<C: Into<ImVec4>>(&mut self, color: ImGuiCol, value: C) {
+        self.style_mut().colors[color as usize] = value.into();
+    }
+    /// Serializes the style as human-editable `key = value` lines, with colors in a
+    /// `[colors]` section named like [`get_style_color_name`].
+    ///
+    /// # Example
+    /// ```text
+    /// alpha = 1.0
+    /// window_padding = [8.0, 8.0]
+    /// anti_aliased_lines = true
+    ///
+    /// [colors]
+    /// Text = [1.0, 1.0, 1.0, 1.0]
+    /// ```
+    pub fn save_style(&self) -> String {
+        style::write_style(self.style())
+    }
+    /// Applies a style saved with [`save_style`](#method.save_style).
+    ///
+    /// Values missing from the text keep their current value. Empty lines and lines starting
+    /// with `#` are ignored. On error, the style is not changed.
+    pub fn load_style(&mut self, text: &str) -> Result<(), StyleParseError> {
+        let mut style = self.style().clone();
+        style::read_style(&mut style, text)?;
+        *self.style_mut() = style;
+        Ok(())
+    }
+    /// Returns the shared font atlas, if this context was created with one.
+    pub fn shared_font_atlas(&self) -> Option<&Arc<Mutex<SharedFontAtlas>>> {
+        self.shared_font_atlas.as_ref()
+    }
     pub fn fonts(&mut self) -> ImFontAtlas {
-        unsafe { ImFontAtlas::from_ptr(self.io_mut().fonts) }
+        let guard = self.activate();
+        unsafe { ImFontAtlas::from_ptr(self.io_mut().fonts, guard) }
     }
     pub fn prepare_texture<'a, F, T>(&mut self, f: F) -> T
     where
         F: FnOnce(TextureHandle<'a>) -> T,
     {
+        // Building the atlas decompresses the default font with global state, so it needs the
+        // context lock like the other font atlas functions
+        let _guard = self.activate();
         let io = self.io();
         let mut pixels: *mut c_uchar = ptr::null_mut();
         let mut width: c_int = 0;
@@ -181,8 +330,15 @@ impl ImGui {
     pub fn set_font_texture_id(&mut self, value: ImTexture) {
         self.fonts().set_texture_id(value.id());
     }
+    /// Set the file used to load and save settings (default: `imgui.ini`).
+    ///
+    /// The file is loaded at the start of the first frame and saved when settings change and
+    /// when the context is dropped. Pass `None` to disable the file.
+    ///
+    /// Once a settings handler is registered, the file is read and written by imgui-rs and the
+    /// filename is no longer passed to dear imgui, so `ImGuiIO::ini_filename` stays null.
     pub fn set_ini_filename(&mut self, value: Option<ImString>) {
-        {
+        if self.settings_handlers.is_empty() {
             let io = self.io_mut();
             io.ini_filename = match value {
                 Some(ref x) => x.as_ptr(),
@@ -201,6 +357,158 @@ impl ImGui {
         }
         self.log_filename = value;
     }
+    /// Replaces the clipboard used for copy and paste.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// let mut imgui = ImGui::init();
+    /// let clipboard = MemoryClipboard::new();
+    /// imgui.set_clipboard_backend(Box::new(clipboard.clone()));
+    /// // ... run frames that copy text ...
+    /// println!("Copied: {:?}", clipboard.contents());
+    /// ```
+    pub fn set_clipboard_backend(&mut self, backend: Box<dyn ClipboardBackend>) {
+        let mut clipboard = Box::new(ClipboardContext::new(backend));
+        {
+            let io = self.io_mut();
+            io.get_clipboard_text_fn = Some(clipboard::get_clipboard_text);
+            io.set_clipboard_text_fn = Some(clipboard::set_clipboard_text);
+            io.clipboard_user_data = &mut *clipboard as *mut ClipboardContext as *mut c_void;
+        }
+        self.clipboard = Some(clipboard);
+    }
+    /// Loads window settings in the ini format, e.g. from a project file.
+    ///
+    /// Should be called before the first frame, because settings are only applied to windows
+    /// that have not been created yet. Use `set_ini_filename(None)` to stop dear imgui from
+    /// also loading and saving the settings on disk.
+    pub fn load_ini_settings(&mut self, data: &str) {
+        let data = self.settings_handlers.read(data);
+        self.settings_loaded = true;
+        let _guard = self.activate();
+        unsafe { sys::igLoadIniSettingsFromMemory(data.as_ptr() as *const c_char, data.len()) }
+    }
+    /// Saves window settings in the ini format and clears `want_save_ini_settings`.
+    pub fn save_ini_settings(&mut self) -> String {
+        let data = {
+            let _guard = self.activate();
+            unsafe { CStr::from_ptr(sys::igSaveIniSettingsToMemory(ptr::null())) }
+        };
+        let mut data = data.to_string_lossy().into_owned();
+        self.settings_handlers.write(&mut data);
+        self.io_mut().want_save_ini_settings = false;
+        self.settings_dirty_timer = 0.0;
+        data
+    }
+    /// Returns true if settings have changed and should be saved with
+    /// [`save_ini_settings`](#method.save_ini_settings).
+    ///
+    /// If an ini filename is set, the settings are also saved to it automatically.
+    pub fn want_save_ini_settings(&self) -> bool {
+        self.io().want_save_ini_settings
+    }
+    /// Marks the settings as changed, e.g. after the state persisted by a settings handler has
+    /// changed.
+    ///
+    /// Like changes to window settings, they are saved after `ini_saving_rate` seconds.
+    pub fn mark_ini_settings_dirty(&mut self) {
+        if self.settings_dirty_timer <= 0.0 {
+            let rate = self.io().ini_saving_rate;
+            if rate > 0.0 {
+                self.settings_dirty_timer = rate;
+            } else {
+                self.io_mut().want_save_ini_settings = true;
+            }
+        }
+    }
+    fn update_settings_dirty_timer(&mut self, delta_time: f32) {
+        if self.settings_dirty_timer > 0.0 {
+            self.settings_dirty_timer -= delta_time;
+            if self.settings_dirty_timer <= 0.0 {
+                self.settings_dirty_timer = 0.0;
+                self.io_mut().want_save_ini_settings = true;
+            }
+        }
+    }
+    /// Registers a handler for the sections with the given type name.
+    ///
+    /// Handlers should be registered before the settings are loaded. Dear imgui drops the
+    /// sections it doesn't know when it saves the ini file, so once a handler is registered
+    /// the file is read and written by imgui-rs instead.
+    pub fn add_settings_handler(&mut self, type_name: &str, handler: Box<dyn SettingsHandler>) {
+        if self.settings_handlers.is_empty() {
+            self.take_over_ini_file();
+        }
+        self.settings_handlers.add(type_name, handler);
+    }
+    /// Persists a value as `key=value` lines in the section `[type_name][name]`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # use std::sync::{Arc, Mutex};
+    /// #[derive(Default)]
+    /// struct Panels {
+    ///     show_log: bool,
+    /// }
+    ///
+    /// impl KeyValueSettings for Panels {
+    ///     fn read(&mut self, key: &str, value: &str) {
+    ///         if key == "ShowLog" {
+    ///             self.show_log = value == "1";
+    ///         }
+    ///     }
+    ///     fn write(&self, out: &mut SettingsWriter) {
+    ///         out.key_value("ShowLog", self.show_log as i32);
+    ///     }
+    /// }
+    ///
+    /// let mut imgui = ImGui::init();
+    /// let panels = Arc::new(Mutex::new(Panels::default()));
+    /// imgui.add_settings("MyApp", "Panels", panels.clone());
+    /// ```
+    pub fn add_settings<T: KeyValueSettings + Send + 'static>(
+        &mut self,
+        type_name: &str,
+        name: &str,
+        value: Arc<Mutex<T>>,
+    ) {
+        let handler = KeyValueHandler::new(name, value);
+        self.add_settings_handler(type_name, Box::new(handler));
+    }
+    fn take_over_ini_file(&mut self) {
+        let io = self.io_mut();
+        let filename = io.ini_filename;
+        io.ini_filename = ptr::null();
+        if !filename.is_null() {
+            // Dear imgui loads the file at the first frame
+            self.settings_loaded |= self.get_frame_count() > 0;
+            if self.ini_filename.is_none() {
+                // The default filename is a static string of dear imgui
+                let filename = unsafe { CStr::from_ptr(filename) };
+                self.ini_filename = Some(ImString::new(filename.to_string_lossy()));
+            }
+        }
+    }
+    fn update_ini_file(&mut self) {
+        if self.settings_handlers.is_empty() {
+            return;
+        }
+        let filename = match self.ini_filename {
+            Some(ref filename) => PathBuf::from(filename.to_str()),
+            None => return,
+        };
+        if !self.settings_loaded {
+            if let Ok(data) = fs::read_to_string(&filename) {
+                self.load_ini_settings(&data);
+            }
+            self.settings_loaded = true;
+        } else if self.want_save_ini_settings() {
+            let data = self.save_ini_settings();
+            let _ = fs::write(&filename, data);
+        }
+    }
     pub fn set_ini_saving_rate(&mut self, value: f32) {
         let io = self.io_mut();
         io.ini_saving_rate = value;
@@ -286,33 +594,40 @@ impl ImGui {
     /// OS cursor is used if `mouse_draw_cursor` is set to `false` with
     /// [set_mouse_draw_cursor](#method.set_mouse_draw_cursor).
     pub fn set_mouse_cursor(&self, cursor: ImGuiMouseCursor) {
+        let _guard = self.activate();
         unsafe {
             sys::igSetMouseCursor(cursor);
         }
     }
     /// Get currently displayed cursor.
     pub fn mouse_cursor(&self) -> ImGuiMouseCursor {
+        let _guard = self.activate();
         unsafe { sys::igGetMouseCursor() }
     }
     /// Returns `true` if mouse is currently dragging with the `button` provided
     /// as argument.
     pub fn is_mouse_dragging(&self, button: ImMouseButton) -> bool {
+        let _guard = self.activate();
         unsafe { sys::igIsMouseDragging(button as c_int, -1.0) }
     }
     /// Returns `true` if the `button` provided as argument is currently down.
     pub fn is_mouse_down(&self, button: ImMouseButton) -> bool {
+        let _guard = self.activate();
         unsafe { sys::igIsMouseDown(button as c_int) }
     }
     /// Returns `true` if the `button` provided as argument is being clicked.
     pub fn is_mouse_clicked(&self, button: ImMouseButton) -> bool {
+        let _guard = self.activate();
         unsafe { sys::igIsMouseClicked(button as c_int, false) }
     }
     /// Returns `true` if the `button` provided as argument is being double-clicked.
     pub fn is_mouse_double_clicked(&self, button: ImMouseButton) -> bool {
+        let _guard = self.activate();
         unsafe { sys::igIsMouseDoubleClicked(button as c_int) }
     }
     /// Returns `true` if the `button` provided as argument was released
     pub fn is_mouse_released(&self, button: ImMouseButton) -> bool {
+        let _guard = self.activate();
         unsafe { sys::igIsMouseReleased(button as c_int) }
     }
     pub fn key_ctrl(&self) -> bool {
@@ -357,6 +672,7 @@ impl ImGui {
     }
     /// Map [`ImGuiKey`] values into user's key index
     pub fn get_key_index(&self, key: ImGuiKey) -> usize {
+        let _guard = self.activate();
         unsafe { sys::igGetKeyIndex(key) as usize }
     }
     /// Return whether specific key is being held
@@ -374,14 +690,17 @@ impl ImGui {
     /// }
     /// ```
     pub fn is_key_down(&self, user_key_index: usize) -> bool {
+        let _guard = self.activate();
         unsafe { sys::igIsKeyDown(user_key_index as c_int) }
     }
     /// Return whether specific key was pressed
     pub fn is_key_pressed(&self, user_key_index: usize) -> bool {
+        let _guard = self.activate();
         unsafe { sys::igIsKeyPressed(user_key_index as c_int, true) }
     }
     /// Return whether specific key was released
     pub fn is_key_released(&self, user_key_index: usize) -> bool {
+        let _guard = self.activate();
         unsafe { sys::igIsKeyReleased(user_key_index as c_int) }
     }
     pub fn add_input_character(&mut self, character: char) {
@@ -392,15 +711,19 @@ impl ImGui {
         }
     }
     pub fn get_time(&self) -> f64 {
+        let _guard = self.activate();
         unsafe { sys::igGetTime() }
     }
     pub fn get_frame_count(&self) -> i32 {
+        let _guard = self.activate();
         unsafe { sys::igGetFrameCount() }
     }
     pub fn get_frame_rate(&self) -> f32 {
         self.io().framerate
     }
     pub fn frame<'ui, 'a: 'ui>(&'a mut self, frame_size: FrameSize, delta_time: f32) -> Ui<'ui> {
+        self.update_settings_dirty_timer(delta_time);
+        self.update_ini_file();
         {
             let io = self.io_mut();
             io.display_size.x = frame_size.logical_size.0 as c_float;
@@ -409,33 +732,45 @@ impl ImGui {
             io.display_framebuffer_scale.y = frame_size.hidpi_factor as c_float;
             io.delta_time = delta_time;
         }
+        // The draw data of the previous frame, and thus its callbacks, are no longer used
+        self.state.draw_callbacks.borrow_mut().clear();
+        // The context stays current until the frame ends
+        let guard = ContextGuard::acquire_for_frame(self.context.ctx);
         unsafe {
             sys::igNewFrame();
-            CURRENT_UI = Some(Ui {
+            *self.state.current_ui.get() = Some(Ui {
                 imgui: mem::transmute(self as &'a ImGui),
                 frame_size,
                 needs_cleanup: false,
+                _context: None,
             });
         }
         Ui {
             imgui: self,
             frame_size,
             needs_cleanup: true,
+            _context: Some(guard),
         }
     }
 }
 
 impl Drop for ImGui {
     fn drop(&mut self) {
+        if self.settings_loaded && !self.settings_handlers.is_empty() {
+            if let Some(ref filename) = self.ini_filename {
+                let filename = PathBuf::from(filename.to_str());
+                let data = self.save_ini_settings();
+                let _ = fs::write(&filename, data);
+            }
+        }
+        let _guard = self.activate();
         unsafe {
-            CURRENT_UI = None;
-            sys::igDestroyContext(ptr::null_mut());
+            *self.state.current_ui.get() = None;
+            sys::igDestroyContext(self.context.ctx);
         }
     }
 }
 
-static mut CURRENT_UI: Option<Ui<'static>> = None;
-
 pub struct DrawData<'a> {
     raw: &'a mut sys::ImDrawData,
 }
@@ -502,10 +837,21 @@ pub struct DrawList<'a> {
     pub vtx_buffer: &'a [sys::ImDrawVert],
 }
 
+impl<'a> DrawList<'a> {
+    /// Returns the commands to execute in order, which renderers should use instead of
+    /// `cmd_buffer` to support callbacks.
+    ///
+    /// Callbacks added through the C API are skipped.
+    pub fn commands(&self) -> impl Iterator<Item = DrawCmd<'a>> {
+        self.cmd_buffer.iter().filter_map(DrawCmd::from_raw)
+    }
+}
+
 pub struct Ui<'ui> {
     imgui: &'ui ImGui,
     frame_size: FrameSize,
     needs_cleanup: bool,
+    _context: Option<ContextGuard>,
 }
 
 static FMT: &'static [u8] = b"%s\0";
@@ -514,6 +860,17 @@ fn fmt_ptr() -> *const c_char {
     FMT.as_ptr() as *const c_char
 }
 
+/// Runs `f`, aborting the process if it panics.
+///
+/// Rust callbacks called by dear imgui must not panic, because unwinding into C code is
+/// undefined behaviour.
+pub(crate) fn abort_on_panic<R, F: FnOnce() -> R>(f: F) -> R {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(_) => process::abort(),
+    }
+}
+
 impl<'ui> Ui<'ui> {
     pub fn frame_size(&self) -> FrameSize {
         self.frame_size
@@ -592,15 +949,22 @@ impl<'a> Drop for Ui<'a> {
         if self.needs_cleanup && !thread::panicking() {
             unsafe {
                 sys::igEndFrame();
-                CURRENT_UI = None;
+                *self.imgui.state.current_ui.get() = None;
             }
         }
     }
 }
 
 impl<'a> Ui<'a> {
+    /// Returns the frame in progress of the current context.
     pub unsafe fn current_ui() -> Option<&'a Ui<'a>> {
-        CURRENT_UI.as_ref()
+        if sys::igGetCurrentContext().is_null() {
+            return None;
+        }
+        let state = (*sys::igGetIO()).user_data as *const ContextState;
+        state
+            .as_ref()
+            .and_then(|state| (*state.current_ui.get()).as_ref())
     }
 }
 
@@ -629,6 +993,124 @@ impl<'ui> Ui<'ui> {
         let size = unsafe { sys::igGetWindowContentRegionMax_nonUDT2() };
         size.into()
     }
+    /// Get current window's width in pixels
+    pub fn get_window_width(&self) -> f32 {
+        unsafe { sys::igGetWindowWidth() }
+    }
+    /// Get current window's height in pixels
+    pub fn get_window_height(&self) -> f32 {
+        unsafe { sys::igGetWindowHeight() }
+    }
+    /// Return `true` if the current window is shown for the first time or after being hidden.
+    pub fn is_window_appearing(&self) -> bool {
+        unsafe { sys::igIsWindowAppearing() }
+    }
+    /// Return `true` if the current window is collapsed.
+    pub fn is_window_collapsed(&self) -> bool {
+        unsafe { sys::igIsWindowCollapsed() }
+    }
+    /// Scales the font of the current window.
+    pub fn set_window_font_scale(&self, scale: f32) {
+        unsafe { sys::igSetWindowFontScale(scale) };
+    }
+    /// Moves the current window. Prefer `Window::position`, which avoids a frame of lag.
+    pub fn set_window_pos(&self, pos: (f32, f32), cond: ImGuiCond) {
+        unsafe { sys::igSetWindowPosVec2(pos.into(), cond) };
+    }
+    /// Resizes the current window. Prefer `Window::size`, which avoids a frame of lag.
+    pub fn set_window_size(&self, size: (f32, f32), cond: ImGuiCond) {
+        unsafe { sys::igSetWindowSizeVec2(size.into(), cond) };
+    }
+    pub fn set_window_collapsed(&self, collapsed: bool, cond: ImGuiCond) {
+        unsafe { sys::igSetWindowCollapsedBool(collapsed, cond) };
+    }
+    /// Focuses the current window and brings it to the front.
+    pub fn set_window_focus(&self) {
+        unsafe { sys::igSetWindowFocus() };
+    }
+    /// Moves the window with the given name.
+    pub fn set_window_pos_by_name(&self, name: &ImStr, pos: (f32, f32), cond: ImGuiCond) {
+        unsafe { sys::igSetWindowPosStr(name.as_ptr(), pos.into(), cond) };
+    }
+    /// Resizes the window with the given name.
+    pub fn set_window_size_by_name(&self, name: &ImStr, size: (f32, f32), cond: ImGuiCond) {
+        unsafe { sys::igSetWindowSizeStr(name.as_ptr(), size.into(), cond) };
+    }
+    /// Collapses or expands the window with the given name.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+    /// if ui.button(im_str!("Collapse all"), (0.0, 0.0)) {
+    ///     for name in &[im_str!("Console"), im_str!("Inspector")] {
+    ///         ui.set_window_collapsed_by_name(name, true, ImGuiCond::Always);
+    ///     }
+    /// }
+    /// ```
+    pub fn set_window_collapsed_by_name(&self, name: &ImStr, collapsed: bool, cond: ImGuiCond) {
+        unsafe { sys::igSetWindowCollapsedStr(name.as_ptr(), collapsed, cond) };
+    }
+    /// Focuses the window with the given name and brings it to the front.
+    pub fn set_window_focus_by_name(&self, name: &ImStr) {
+        unsafe { sys::igSetWindowFocusStr(name.as_ptr()) };
+    }
+}
+
+// Scrolling
+impl<'ui> Ui<'ui> {
+    /// Get current window's horizontal scroll position, between 0.0 and `get_scroll_max_x()`
+    pub fn get_scroll_x(&self) -> f32 {
+        unsafe { sys::igGetScrollX() }
+    }
+    /// Get current window's vertical scroll position, between 0.0 and `get_scroll_max_y()`
+    pub fn get_scroll_y(&self) -> f32 {
+        unsafe { sys::igGetScrollY() }
+    }
+    pub fn get_scroll_max_x(&self) -> f32 {
+        unsafe { sys::igGetScrollMaxX() }
+    }
+    pub fn get_scroll_max_y(&self) -> f32 {
+        unsafe { sys::igGetScrollMaxY() }
+    }
+    pub fn set_scroll_x(&self, scroll_x: f32) {
+        unsafe { sys::igSetScrollX(scroll_x) };
+    }
+    pub fn set_scroll_y(&self, scroll_y: f32) {
+        unsafe { sys::igSetScrollY(scroll_y) };
+    }
+    /// Scrolls to make the cursor position visible, e.g. to scroll to the last submitted item.
+    ///
+    /// A ratio of 0.0 puts it at the top, 0.5 in the center and 1.0 at the bottom.
+    pub fn set_scroll_here_y(&self, center_y_ratio: f32) {
+        unsafe { sys::igSetScrollHereY(center_y_ratio) };
+    }
+    /// Scrolls to make the local position `pos_y` visible.
+    pub fn set_scroll_from_pos_y(&self, pos_y: f32, center_y_ratio: f32) {
+        unsafe { sys::igSetScrollFromPosY(pos_y, center_y_ratio) };
+    }
+    /// Runs a function that appends content to the current window, and keeps the window
+    /// scrolled to the bottom if it was at the bottom before.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+    /// let lines = vec!["first", "second"];
+    /// let mut auto_scroll = AutoScroll::new();
+    /// ui.child_frame(im_str!("log"), (0.0, 0.0))
+    ///     .auto_scroll(&mut auto_scroll)
+    ///     .build(|| {
+    ///         for line in &lines {
+    ///             ui.text(line);
+    ///         }
+    ///     });
+    /// ```
+    pub fn auto_scroll<F: FnOnce()>(&self, state: &mut AutoScroll, f: F) {
+        state.build(f);
+    }
 }
 
 // Layout
@@ -818,6 +1300,52 @@ impl<'ui> Ui<'ui> {
         f();
         self.pop_id();
     }
+
+    /// Computes the id of `id` combined with the ID stack, as used by widgets.
+    pub fn get_id<'a, I: Into<ImId<'a>>>(&self, id: I) -> ImGuiID {
+        unsafe {
+            match id.into() {
+                // Dear imgui hashes integer ids as pointers
+                ImId::Int(i) => sys::igGetIDPtr(i as isize as *const c_void),
+                ImId::Str(s) => {
+                    let start = s.as_ptr() as *const c_char;
+                    let end = start.add(s.len());
+                    sys::igGetIDRange(start, end)
+                }
+                ImId::Ptr(p) => sys::igGetIDPtr(p),
+            }
+        }
+    }
+}
+
+// State storage
+impl<'ui> Ui<'ui> {
+    /// Returns the state storage of the current window.
+    ///
+    /// This borrows the `Ui` mutably, so only one handle to the storage exists at a time, and a
+    /// handle can't outlive a storage set with `with_state_storage`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let mut ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+    /// let id = ui.get_id("clicks");
+    /// let clicks = ui.storage().get_i32(id, 0);
+    /// if ui.button(im_str!("Click me"), (0.0, 0.0)) {
+    ///     ui.storage().set_i32(id, clicks + 1);
+    /// }
+    /// ```
+    pub fn storage(&mut self) -> Storage<'_> {
+        unsafe { Storage::from_ptr(sys::igGetStateStorage()) }
+    }
+    /// Runs a function with `storage` as the state storage of the current window.
+    pub fn with_state_storage<F: FnOnce()>(&self, storage: &mut StateStorage, f: F) {
+        let previous = unsafe { sys::igGetStateStorage() };
+        unsafe { sys::igSetStateStorage(storage.raw()) };
+        f();
+        unsafe { sys::igSetStateStorage(previous) };
+    }
 }
 
 // Widgets
@@ -894,6 +1422,14 @@ impl<'ui> Ui<'ui> {
 
 // Widgets: Input
 impl<'ui> Ui<'ui> {
+    /// Creates a numeric input for any primitive numeric type, or an array of 2 to 4 of them.
+    pub fn input_scalar<'p, T: DataType>(
+        &self,
+        label: &'p ImStr,
+        value: &'p mut T,
+    ) -> InputScalar<'ui, 'p, T> {
+        InputScalar::new(self, label, value)
+    }
     pub fn input_text<'p>(&self, label: &'p ImStr, buf: &'p mut ImString) -> InputText<'ui, 'p> {
         InputText::new(self, label, buf)
     }
@@ -945,6 +1481,25 @@ impl<'ui> Ui<'ui> {
 
 // Widgets: Drag
 impl<'ui> Ui<'ui> {
+    /// Creates a drag widget for any primitive numeric type, or an array of 2 to 4 of them.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+    /// let mut volume: u8 = 128;
+    /// let mut offset: [f64; 3] = [0.0; 3];
+    /// ui.drag_scalar(im_str!("Volume"), &mut volume).build();
+    /// ui.drag_scalar(im_str!("Offset"), &mut offset).speed(0.01).build();
+    /// ```
+    pub fn drag_scalar<'p, T: DataType>(
+        &self,
+        label: &'p ImStr,
+        value: &'p mut T,
+    ) -> DragScalar<'ui, 'p, T> {
+        DragScalar::new(self, label, value)
+    }
     pub fn drag_float<'p>(&self, label: &'p ImStr, value: &'p mut f32) -> DragFloat<'ui, 'p> {
         DragFloat::new(self, label, value)
     }
@@ -1001,6 +1556,16 @@ impl<'ui> Ui<'ui> {
 
 // Widgets: Sliders
 impl<'ui> Ui<'ui> {
+    /// Creates a slider for any primitive numeric type, or an array of 2 to 4 of them.
+    pub fn slider_scalar<'p, T: DataType>(
+        &self,
+        label: &'p ImStr,
+        value: &'p mut T,
+        min: T::Scalar,
+        max: T::Scalar,
+    ) -> SliderScalar<'ui, 'p, T> {
+        SliderScalar::new(self, label, value, min, max)
+    }
     pub fn slider_float<'p>(
         &self,
         label: &'p ImStr,
@@ -1252,10 +1817,70 @@ impl<'ui> Ui<'ui> {
     pub fn close_current_popup(&self) {
         unsafe { sys::igCloseCurrentPopup() };
     }
+    /// Opens a popup when the previous item is clicked with `button`.
+    ///
+    /// Returns true if the popup was opened.
+    pub fn open_popup_on_item_click(&self, str_id: &ImStr, button: ImMouseButton) -> bool {
+        unsafe { sys::igOpenPopupOnItemClick(str_id.as_ptr(), button as c_int) }
+    }
+    /// Returns true if the popup is open at the current level of the popup stack.
+    pub fn is_popup_open(&self, str_id: &ImStr) -> bool {
+        unsafe { sys::igIsPopupOpen(str_id.as_ptr()) }
+    }
+    /// Get the mouse position when the current popup was opened, e.g. to add an item at the
+    /// clicked position from a context menu.
+    pub fn get_mouse_pos_on_opening_current_popup(&self) -> (f32, f32) {
+        let pos = unsafe { sys::igGetMousePosOnOpeningCurrentPopup_nonUDT2() };
+        pos.into()
+    }
+    /// Creates a context menu for the previous item.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+    /// ui.tree_node(im_str!("Node")).build(|| {});
+    /// ui.context_menu_item().build(|| {
+    ///     if ui.menu_item(im_str!("Rename")).build() {
+    ///         // ...
+    ///     }
+    /// });
+    /// ```
+    pub fn context_menu_item<'p>(&self) -> ContextMenu<'ui, 'p> {
+        ContextMenu::item(self)
+    }
+    /// Creates a context menu for the current window.
+    pub fn context_menu_window<'p>(&self) -> ContextMenu<'ui, 'p> {
+        ContextMenu::window(self)
+    }
+    /// Creates a context menu that opens when clicking outside of all windows.
+    pub fn context_menu_void<'p>(&self) -> ContextMenu<'ui, 'p> {
+        ContextMenu::void(self)
+    }
 }
 
 // Widgets: Combos
 impl<'ui> Ui<'ui> {
+    /// Creates a combo box with custom contents or items of any type.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # use std::borrow::Cow;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+    /// let sizes = [8, 16, 32, 64];
+    /// let mut size = 16;
+    /// ui.combo_box(im_str!("Size"))
+    ///     .height_large()
+    ///     .build_simple_value(&sizes, &mut size, |size| {
+    ///         Cow::Owned(ImString::new(format!("{} px", size)))
+    ///     });
+    /// ```
+    pub fn combo_box<'p>(&self, label: &'p ImStr) -> ComboBox<'ui, 'p> {
+        ComboBox::new(self, label)
+    }
     pub fn combo<'p>(
         &self,
         label: &'p ImStr,
@@ -1278,6 +1903,10 @@ impl<'ui> Ui<'ui> {
 
 // Widgets: ListBox
 impl<'ui> Ui<'ui> {
+    /// Creates a list box with custom contents or items of any type.
+    pub fn list_box_header<'p>(&self, label: &'p ImStr) -> ListBoxHeader<'ui, 'p> {
+        ListBoxHeader::new(self, label)
+    }
     pub fn list_box<'p>(
         &self,
         label: &'p ImStr,
@@ -1298,6 +1927,118 @@ impl<'ui> Ui<'ui> {
     }
 }
 
+// Widgets: Table
+impl<'ui> Ui<'ui> {
+    /// Creates a table with a header row, sorting and row selection.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+    /// let mut files = vec![("a.txt", 120), ("b.txt", 40)];
+    /// let mut selected = None;
+    /// let columns = [
+    ///     TableColumn::new(im_str!("Name")).sortable(true),
+    ///     TableColumn::new(im_str!("Size")).sortable(true).width(80.0),
+    /// ];
+    /// ui.table(im_str!("files"), &columns).build(|table| {
+    ///     if let Some(sort) = table.sort_spec() {
+    ///         match sort.column {
+    ///             0 => files.sort_by_key(|file| file.0),
+    ///             _ => files.sort_by_key(|file| file.1),
+    ///         }
+    ///         if sort.direction == SortDirection::Descending {
+    ///             files.reverse();
+    ///         }
+    ///     }
+    ///     for (index, file) in files.iter().enumerate() {
+    ///         if table.row(im_str!("{}", file.0), selected == Some(index), |_| {
+    ///             ui.text(im_str!("{}", file.1));
+    ///         }) {
+    ///             selected = Some(index);
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn table<'p>(&self, id: &'p ImStr, columns: &'p [TableColumn<'p>]) -> Table<'ui, 'p> {
+        Table::new(self, id, columns)
+    }
+}
+
+// Widgets: List clipper
+impl<'ui> Ui<'ui> {
+    /// Submits only the visible items of a long list of items with equal height.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+    /// let lines: Vec<String> = (0..1_000_000).map(|i| format!("Line {}", i)).collect();
+    /// ui.child_frame(im_str!("log"), (0.0, 0.0)).build(|| {
+    ///     ui.list_clipper(lines.len()).build(|range| {
+    ///         for line in &lines[range] {
+    ///             ui.text(line);
+    ///         }
+    ///     });
+    /// });
+    /// ```
+    pub fn list_clipper(&self, items_count: usize) -> ListClipper<'ui> {
+        ListClipper::new(self, items_count)
+    }
+}
+
+// Widgets: Drag and drop
+impl<'ui> Ui<'ui> {
+    /// Makes the previously submitted item a drag and drop source.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+    /// let items = [im_str!("A"), im_str!("B"), im_str!("C")];
+    /// for (i, item) in items.iter().enumerate() {
+    ///     ui.button(item, (0.0, 0.0));
+    ///     ui.drag_drop_source(im_str!("ITEM_INDEX")).build(i, || {
+    ///         ui.text(im_str!("Moving {}", item.to_str()));
+    ///     });
+    ///     ui.drag_drop_target(|target| {
+    ///         let flags = ImGuiDragDropFlags::empty();
+    ///         let payload = target.accept_payload::<usize>(im_str!("ITEM_INDEX"), flags);
+    ///         if let Some(Ok(payload)) = payload {
+    ///             println!("Move item {} to {}", payload.data, i);
+    ///         }
+    ///     });
+    /// }
+    /// ```
+    pub fn drag_drop_source<'p>(&self, name: &'p ImStr) -> DragDropSource<'ui, 'p> {
+        DragDropSource::new(self, name)
+    }
+    /// Makes the previously submitted item a drag and drop target.
+    ///
+    /// The closure is only called while a payload is hovering the item.
+    /// Returns true if the closure was called.
+    pub fn drag_drop_target<F: FnOnce(&DragDropTarget<'ui>)>(&self, f: F) -> bool {
+        let render = unsafe { sys::igBeginDragDropTarget() };
+        if render {
+            f(&DragDropTarget::new(self));
+            unsafe { sys::igEndDragDropTarget() };
+        }
+        render
+    }
+    /// Peeks at the payload currently being dragged, if it has the given name.
+    ///
+    /// This can be called anywhere, e.g. to highlight valid targets.
+    pub fn drag_drop_payload<T: Copy + 'static>(
+        &self,
+        name: &ImStr,
+    ) -> Option<Result<DragDropPayload<T>, PayloadIsWrongType>> {
+        drag_drop::current_payload(name)
+    }
+}
+
 // Widgets: Radio
 impl<'ui> Ui<'ui> {
     /// Creates a radio button for selecting an integer value.
@@ -1604,6 +2345,51 @@ impl<'ui> Ui<'ui> {
     }
 }
 
+// Logging
+impl<'ui> Ui<'ui> {
+    /// Captures the text of all widgets submitted by `f`.
+    ///
+    /// Tree nodes deeper than `max_depth` are logged closed (default: 2). Returns the captured
+    /// text if the target is `LogTarget::Buffer`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+    /// let text = ui.log_to(LogTarget::Buffer, None, || {
+    ///     ui.text("Hello");
+    ///     ui.text("world");
+    /// });
+    /// println!("{}", text.unwrap());
+    /// ```
+    pub fn log_to<F: FnOnce()>(
+        &self,
+        target: LogTarget,
+        max_depth: Option<u32>,
+        f: F,
+    ) -> Option<String> {
+        let max_depth = max_depth.map(|depth| depth as c_int).unwrap_or(-1);
+        match target {
+            LogTarget::Tty => unsafe { sys::igLogToTTY(max_depth) },
+            LogTarget::File(filename) => {
+                let filename = filename.map(|f| f.as_ptr()).unwrap_or(ptr::null());
+                unsafe { sys::igLogToFile(max_depth, filename) }
+            }
+            LogTarget::Clipboard => unsafe { sys::igLogToClipboard(max_depth) },
+            LogTarget::Buffer => return Some(logging::capture(max_depth, f)),
+        }
+        f();
+        unsafe { sys::igLogFinish() };
+        None
+    }
+    /// Draws buttons for starting to log the current window to the tty, a file or the
+    /// clipboard.
+    pub fn log_buttons(&self) {
+        unsafe { sys::igLogButtons() };
+    }
+}
+
 /// # Utilities
 impl<'ui> Ui<'ui> {
     /// Returns `true` if the last item is being hovered by the mouse.
@@ -1704,4 +2490,30 @@ impl<'ui> Ui<'ui> {
     pub fn get_window_draw_list(&'ui self) -> WindowDrawList<'ui> {
         WindowDrawList::new(self)
     }
+
+    /// Get access to drawing API on top of all windows
+    ///
+    /// The overlay draw list is not clipped to any window, so positions are in
+    /// screen coordinates and it can be used anywhere in the frame, e.g. for
+    /// debug annotations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// fn crosshair(ui: &Ui) {
+    ///     let (x, y) = ui.imgui().mouse_pos();
+    ///     let draw_list = ui.get_overlay_draw_list();
+    ///     const RED: [f32; 3] = [1.0, 0.0, 0.0];
+    ///     draw_list.add_line([x - 10.0, y], [x + 10.0, y], RED).build();
+    ///     draw_list.add_line([x, y - 10.0], [x, y + 10.0], RED).build();
+    /// }
+    /// ```
+    ///
+    /// Like [`Ui::get_window_draw_list`], this function will panic if
+    /// several instances of the overlay draw list coexist. It can be used at
+    /// the same time as the window draw list.
+    pub fn get_overlay_draw_list(&'ui self) -> WindowDrawList<'ui> {
+        WindowDrawList::overlay(self)
+    }
 }
@@ -0,0 +1,74 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use sys;
+
+use super::combo_box::{position, select_items};
+use super::{ImStr, ImVec2, TextFilter, Ui};
+
+/// Created by call to [`Ui::list_box_header`].
+#[must_use]
+pub struct ListBoxHeader<'ui, 'p> {
+    label: &'p ImStr,
+    size: ImVec2,
+    filter: Option<&'p TextFilter>,
+    _phantom: PhantomData<&'ui Ui<'ui>>,
+}
+
+impl<'ui, 'p> ListBoxHeader<'ui, 'p> {
+    pub fn new(_: &Ui<'ui>, label: &'p ImStr) -> Self {
+        ListBoxHeader {
+            label,
+            size: ImVec2::new(0.0, 0.0),
+            filter: None,
+            _phantom: PhantomData,
+        }
+    }
+    /// Set the size of the list box (default: full width and ~7 items high).
+    #[inline]
+    pub fn size<S: Into<ImVec2>>(mut self, size: S) -> Self {
+        self.size = size.into();
+        self
+    }
+    /// Only show the items whose label passes `filter` in `build_simple`.
+    #[inline]
+    pub fn filter(mut self, filter: &'p TextFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+    /// Calls `f` to submit the contents of the list box if it is visible.
+    ///
+    /// Returns true if the list box is visible.
+    pub fn build<F: FnOnce()>(self, f: F) -> bool {
+        let render = unsafe { sys::igListBoxHeaderVec2(self.label.as_ptr(), self.size) };
+        if render {
+            f();
+            unsafe { sys::igListBoxFooter() };
+        }
+        render
+    }
+    /// Submits a selectable for each item, labeled by `label_fn`.
+    ///
+    /// Returns true if the selection was changed.
+    pub fn build_simple<T, L>(self, items: &[T], current_item: &mut usize, label_fn: L) -> bool
+    where
+        L: Fn(&T) -> Cow<ImStr>,
+    {
+        let mut changed = false;
+        let filter = self.filter;
+        self.build(|| changed = select_items(items, current_item, &label_fn, filter));
+        changed
+    }
+    /// Like `build_simple`, but the selection is the item itself.
+    pub fn build_simple_value<T, L>(self, items: &[T], current_item: &mut T, label_fn: L) -> bool
+    where
+        T: Clone + PartialEq,
+        L: Fn(&T) -> Cow<ImStr>,
+    {
+        let mut index = position(items, current_item);
+        let changed = self.build_simple(items, &mut index, label_fn);
+        if changed {
+            *current_item = items[index].clone();
+        }
+        changed
+    }
+}
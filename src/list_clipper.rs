@@ -0,0 +1,71 @@
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::os::raw::c_int;
+
+use sys;
+
+use super::Ui;
+
+/// Created by call to [`Ui::list_clipper`].
+#[must_use]
+pub struct ListClipper<'ui> {
+    items_count: usize,
+    items_height: f32,
+    _phantom: PhantomData<&'ui Ui<'ui>>,
+}
+
+impl<'ui> ListClipper<'ui> {
+    pub fn new(_: &Ui<'ui>, items_count: usize) -> Self {
+        ListClipper {
+            items_count,
+            items_height: -1.0,
+            _phantom: PhantomData,
+        }
+    }
+    /// Set the height of a single item including spacing (default: measured from the first
+    /// item).
+    #[inline]
+    pub fn items_height(mut self, items_height: f32) -> Self {
+        self.items_height = items_height;
+        self
+    }
+    /// Calls `f` with the ranges of item indices that need to be submitted.
+    ///
+    /// If the item height was not set, the first call receives a range containing only the
+    /// first item, which is used to measure the height.
+    ///
+    /// Panics if the number of items doesn't fit in an `i32`.
+    pub fn build<F: FnMut(Range<usize>)>(self, mut f: F) {
+        assert!(
+            self.items_count <= c_int::max_value() as usize,
+            "ListClipper supports up to i32::MAX items"
+        );
+        let mut clipper = Clipper(sys::ImGuiListClipper {
+            start_pos_y: 0.0,
+            items_height: 0.0,
+            items_count: -1,
+            step_no: 0,
+            display_start: -1,
+            display_end: 0,
+        });
+        unsafe {
+            sys::ImGuiListClipper_Begin(
+                &mut clipper.0,
+                self.items_count as c_int,
+                self.items_height,
+            );
+        }
+        while unsafe { sys::ImGuiListClipper_Step(&mut clipper.0) } {
+            f(clipper.0.display_start as usize..clipper.0.display_end as usize);
+        }
+    }
+}
+
+/// Ends the clipper even if the closure panics. Ending twice is a no-op.
+struct Clipper(sys::ImGuiListClipper);
+
+impl Drop for Clipper {
+    fn drop(&mut self) {
+        unsafe { sys::ImGuiListClipper_End(&mut self.0) };
+    }
+}
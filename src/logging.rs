@@ -0,0 +1,45 @@
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+use sys;
+
+use super::{abort_on_panic, ImStr};
+
+/// Where [`Ui::log_to`] writes the captured text.
+#[derive(Copy, Clone, Debug)]
+pub enum LogTarget<'a> {
+    /// Standard output
+    Tty,
+    /// A file, or the file set with `ImGui::set_log_filename` if `None`
+    File(Option<&'a ImStr>),
+    /// The clipboard backend
+    Clipboard,
+    /// A string returned by `Ui::log_to`
+    Buffer,
+}
+
+extern "C" fn capture_text(user_data: *mut c_void, text: *const c_char) {
+    abort_on_panic(|| {
+        let buf = unsafe { &mut *(user_data as *mut String) };
+        let text = unsafe { CStr::from_ptr(text) };
+        buf.push_str(&text.to_string_lossy());
+    })
+}
+
+/// Calls `f` while logging to the clipboard, and intercepts the text dear imgui sets as the
+/// clipboard contents when the log is finished.
+pub(crate) fn capture<F: FnOnce()>(max_depth: i32, f: F) -> String {
+    unsafe { sys::igLogToClipboard(max_depth) };
+    f();
+    let mut buf = String::new();
+    unsafe {
+        let io = &mut *sys::igGetIO();
+        let set_clipboard_text_fn = io.set_clipboard_text_fn;
+        let clipboard_user_data = io.clipboard_user_data;
+        io.set_clipboard_text_fn = Some(capture_text);
+        io.clipboard_user_data = &mut buf as *mut String as *mut c_void;
+        sys::igLogFinish();
+        io.set_clipboard_text_fn = set_clipboard_text_fn;
+        io.clipboard_user_data = clipboard_user_data;
+    }
+    buf
+}
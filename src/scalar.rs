@@ -0,0 +1,453 @@
+use std::marker::PhantomData;
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr;
+use std::slice;
+use std::{f32, f64};
+use sys;
+use sys::ImGuiDataType;
+
+use super::{ImGuiInputTextFlags, ImStr, Ui};
+
+/// A primitive numeric type that can be edited by the scalar widgets.
+///
+/// Types that dear imgui doesn't support directly (e.g. `u8` or `i16`) are edited as a wider
+/// type and clamped to their range.
+pub trait ScalarType: Copy + PartialOrd {
+    /// Type that dear imgui edits
+    type Native: Copy + Default + PartialOrd;
+    const DATA_TYPE: ImGuiDataType;
+    fn min_value() -> Self;
+    fn max_value() -> Self;
+    fn to_native(self) -> Self::Native;
+    /// Converts back, clamping to the range of `Self`.
+    fn from_native(value: Self::Native) -> Self;
+}
+
+macro_rules! impl_scalar_type {
+    ($T:ident, $Native:ty, $DataType:ident, $min:expr, $max:expr) => {
+        impl ScalarType for $T {
+            type Native = $Native;
+            const DATA_TYPE: ImGuiDataType = ImGuiDataType::$DataType;
+            #[inline]
+            fn min_value() -> $T {
+                $min
+            }
+            #[inline]
+            fn max_value() -> $T {
+                $max
+            }
+            #[inline]
+            fn to_native(self) -> $Native {
+                self as $Native
+            }
+            #[inline]
+            fn from_native(value: $Native) -> $T {
+                if value <= Self::min_value() as $Native {
+                    Self::min_value()
+                } else if value >= Self::max_value() as $Native {
+                    Self::max_value()
+                } else {
+                    value as $T
+                }
+            }
+        }
+    };
+    ($T:ident, $Native:ty, $DataType:ident) => {
+        impl_scalar_type!($T, $Native, $DataType, $T::min_value(), $T::max_value());
+    };
+}
+
+impl_scalar_type!(i8, i32, S32);
+impl_scalar_type!(u8, u32, U32);
+impl_scalar_type!(i16, i32, S32);
+impl_scalar_type!(u16, u32, U32);
+impl_scalar_type!(i32, i32, S32);
+impl_scalar_type!(u32, u32, U32);
+impl_scalar_type!(i64, i64, S64);
+impl_scalar_type!(u64, u64, U64);
+impl_scalar_type!(isize, i64, S64);
+impl_scalar_type!(usize, u64, U64);
+impl_scalar_type!(f32, f32, Float, f32::MIN, f32::MAX);
+impl_scalar_type!(f64, f64, Double, f64::MIN, f64::MAX);
+
+/// A value edited by the scalar widgets: a primitive numeric type or an array of 2 to 4 of
+/// them.
+pub trait DataType {
+    type Scalar: ScalarType;
+    fn components(&self) -> &[Self::Scalar];
+    fn components_mut(&mut self) -> &mut [Self::Scalar];
+}
+
+impl<T: ScalarType> DataType for T {
+    type Scalar = T;
+    fn components(&self) -> &[T] {
+        slice::from_ref(self)
+    }
+    fn components_mut(&mut self) -> &mut [T] {
+        slice::from_mut(self)
+    }
+}
+
+macro_rules! impl_data_type_array {
+    ($N:expr) => {
+        impl<T: ScalarType> DataType for [T; $N] {
+            type Scalar = T;
+            fn components(&self) -> &[T] {
+                self
+            }
+            fn components_mut(&mut self) -> &mut [T] {
+                self
+            }
+        }
+    };
+}
+
+impl_data_type_array!(2);
+impl_data_type_array!(3);
+impl_data_type_array!(4);
+
+type Native<T> = <<T as DataType>::Scalar as ScalarType>::Native;
+
+/// Edits the value as its native type. `f` receives a pointer to the components and the
+/// number of components.
+fn edit_native<T, F>(value: &mut T, f: F) -> bool
+where
+    T: DataType + ?Sized,
+    F: FnOnce(*mut c_void, c_int) -> bool,
+{
+    let components = value.components_mut();
+    let mut native = [Native::<T>::default(); 4];
+    for (native, component) in native.iter_mut().zip(components.iter()) {
+        *native = component.to_native();
+    }
+    let changed = f(
+        native.as_mut_ptr() as *mut c_void,
+        components.len() as c_int,
+    );
+    if changed {
+        for (component, &native) in components.iter_mut().zip(native.iter()) {
+            *component = ScalarType::from_native(native);
+        }
+    }
+    changed
+}
+
+fn native_ptr<N>(value: &Option<N>) -> *const c_void {
+    match *value {
+        Some(ref value) => value as *const N as *const c_void,
+        None => ptr::null(),
+    }
+}
+
+fn format_ptr(format: Option<&ImStr>) -> *const c_char {
+    format.map(|f| f.as_ptr()).unwrap_or(ptr::null())
+}
+
+/// Created by call to [`Ui::drag_scalar`].
+#[must_use]
+pub struct DragScalar<'ui, 'p, T: DataType + 'p> {
+    label: &'p ImStr,
+    value: &'p mut T,
+    speed: f32,
+    min: T::Scalar,
+    max: T::Scalar,
+    display_format: Option<&'p ImStr>,
+    power: f32,
+    _phantom: PhantomData<&'ui Ui<'ui>>,
+}
+
+impl<'ui, 'p, T: DataType + 'p> DragScalar<'ui, 'p, T> {
+    pub fn new(_: &Ui<'ui>, label: &'p ImStr, value: &'p mut T) -> Self {
+        DragScalar {
+            label,
+            value,
+            speed: 1.0,
+            min: T::Scalar::min_value(),
+            max: T::Scalar::max_value(),
+            display_format: None,
+            power: 1.0,
+            _phantom: PhantomData,
+        }
+    }
+    #[inline]
+    pub fn speed(mut self, value: f32) -> Self {
+        self.speed = value;
+        self
+    }
+    #[inline]
+    pub fn min(mut self, value: T::Scalar) -> Self {
+        self.min = value;
+        self
+    }
+    #[inline]
+    pub fn max(mut self, value: T::Scalar) -> Self {
+        self.max = value;
+        self
+    }
+    /// Set the printf-style format (default: depends on the data type).
+    #[inline]
+    pub fn display_format(mut self, display_format: &'p ImStr) -> Self {
+        self.display_format = Some(display_format);
+        self
+    }
+    #[inline]
+    pub fn power(mut self, value: f32) -> Self {
+        self.power = value;
+        self
+    }
+    pub fn build(self) -> bool {
+        let min = Some(self.min.to_native());
+        let max = Some(self.max.to_native());
+        let (label, speed, power) = (self.label, self.speed, self.power);
+        let format = format_ptr(self.display_format);
+        edit_native(self.value, |data, components| unsafe {
+            if components == 1 {
+                sys::igDragScalar(
+                    label.as_ptr(),
+                    T::Scalar::DATA_TYPE,
+                    data,
+                    speed,
+                    native_ptr(&min),
+                    native_ptr(&max),
+                    format,
+                    power,
+                )
+            } else {
+                sys::igDragScalarN(
+                    label.as_ptr(),
+                    T::Scalar::DATA_TYPE,
+                    data,
+                    components,
+                    speed,
+                    native_ptr(&min),
+                    native_ptr(&max),
+                    format,
+                    power,
+                )
+            }
+        })
+    }
+}
+
+/// Created by call to [`Ui::slider_scalar`].
+#[must_use]
+pub struct SliderScalar<'ui, 'p, T: DataType + 'p> {
+    label: &'p ImStr,
+    value: &'p mut T,
+    min: T::Scalar,
+    max: T::Scalar,
+    display_format: Option<&'p ImStr>,
+    power: f32,
+    _phantom: PhantomData<&'ui Ui<'ui>>,
+}
+
+impl<'ui, 'p, T: DataType + 'p> SliderScalar<'ui, 'p, T> {
+    pub fn new(
+        _: &Ui<'ui>,
+        label: &'p ImStr,
+        value: &'p mut T,
+        min: T::Scalar,
+        max: T::Scalar,
+    ) -> Self {
+        SliderScalar {
+            label,
+            value,
+            min,
+            max,
+            display_format: None,
+            power: 1.0,
+            _phantom: PhantomData,
+        }
+    }
+    /// Set the printf-style format (default: depends on the data type).
+    #[inline]
+    pub fn display_format(mut self, display_format: &'p ImStr) -> Self {
+        self.display_format = Some(display_format);
+        self
+    }
+    #[inline]
+    pub fn power(mut self, value: f32) -> Self {
+        self.power = value;
+        self
+    }
+    pub fn build(self) -> bool {
+        let min = Some(self.min.to_native());
+        let max = Some(self.max.to_native());
+        let (label, power) = (self.label, self.power);
+        let format = format_ptr(self.display_format);
+        edit_native(self.value, |data, components| unsafe {
+            if components == 1 {
+                sys::igSliderScalar(
+                    label.as_ptr(),
+                    T::Scalar::DATA_TYPE,
+                    data,
+                    native_ptr(&min),
+                    native_ptr(&max),
+                    format,
+                    power,
+                )
+            } else {
+                sys::igSliderScalarN(
+                    label.as_ptr(),
+                    T::Scalar::DATA_TYPE,
+                    data,
+                    components,
+                    native_ptr(&min),
+                    native_ptr(&max),
+                    format,
+                    power,
+                )
+            }
+        })
+    }
+}
+
+/// Created by call to [`Ui::input_scalar`].
+#[must_use]
+pub struct InputScalar<'ui, 'p, T: DataType + 'p> {
+    label: &'p ImStr,
+    value: &'p mut T,
+    step: Option<T::Scalar>,
+    step_fast: Option<T::Scalar>,
+    display_format: Option<&'p ImStr>,
+    flags: ImGuiInputTextFlags,
+    _phantom: PhantomData<&'ui Ui<'ui>>,
+}
+
+impl<'ui, 'p, T: DataType + 'p> InputScalar<'ui, 'p, T> {
+    pub fn new(_: &Ui<'ui>, label: &'p ImStr, value: &'p mut T) -> Self {
+        InputScalar {
+            label,
+            value,
+            step: None,
+            step_fast: None,
+            display_format: None,
+            flags: ImGuiInputTextFlags::empty(),
+            _phantom: PhantomData,
+        }
+    }
+    /// Set the step of the +/- buttons (default: no buttons).
+    #[inline]
+    pub fn step(mut self, value: T::Scalar) -> Self {
+        self.step = Some(value);
+        self
+    }
+    /// Set the step of the +/- buttons while holding Ctrl.
+    #[inline]
+    pub fn step_fast(mut self, value: T::Scalar) -> Self {
+        self.step_fast = Some(value);
+        self
+    }
+    /// Set the printf-style format (default: depends on the data type).
+    #[inline]
+    pub fn display_format(mut self, display_format: &'p ImStr) -> Self {
+        self.display_format = Some(display_format);
+        self
+    }
+    #[inline]
+    pub fn flags(mut self, flags: ImGuiInputTextFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+    #[inline]
+    pub fn read_only(mut self, value: bool) -> Self {
+        self.flags.set(ImGuiInputTextFlags::ReadOnly, value);
+        self
+    }
+    #[inline]
+    pub fn enter_returns_true(mut self, value: bool) -> Self {
+        self.flags.set(ImGuiInputTextFlags::EnterReturnsTrue, value);
+        self
+    }
+    pub fn build(self) -> bool {
+        let step = self.step.map(ScalarType::to_native);
+        let step_fast = self.step_fast.map(ScalarType::to_native);
+        let (label, flags) = (self.label, self.flags);
+        let format = format_ptr(self.display_format);
+        edit_native(self.value, |data, components| unsafe {
+            if components == 1 {
+                sys::igInputScalar(
+                    label.as_ptr(),
+                    T::Scalar::DATA_TYPE,
+                    data,
+                    native_ptr(&step),
+                    native_ptr(&step_fast),
+                    format,
+                    flags,
+                )
+            } else {
+                sys::igInputScalarN(
+                    label.as_ptr(),
+                    T::Scalar::DATA_TYPE,
+                    data,
+                    components,
+                    native_ptr(&step),
+                    native_ptr(&step_fast),
+                    format,
+                    flags,
+                )
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn narrow_types_are_widened() {
+        assert_eq!(ScalarType::to_native(-128i8), -128i32);
+        assert_eq!(ScalarType::to_native(255u8), 255u32);
+        assert_eq!(ScalarType::to_native(-2i16), -2i32);
+        assert_eq!(
+            ScalarType::to_native(usize::max_value()),
+            usize::max_value() as u64
+        );
+        assert_eq!(ScalarType::to_native(1.5f32), 1.5f32);
+        assert_eq!(<u8 as ScalarType>::DATA_TYPE, ImGuiDataType::U32);
+        assert_eq!(<isize as ScalarType>::DATA_TYPE, ImGuiDataType::S64);
+    }
+
+    #[test]
+    fn native_values_are_clamped() {
+        assert_eq!(<u8 as ScalarType>::from_native(300), 255);
+        assert_eq!(<u8 as ScalarType>::from_native(42), 42);
+        assert_eq!(<i8 as ScalarType>::from_native(-200), -128);
+        assert_eq!(<i8 as ScalarType>::from_native(200), 127);
+        assert_eq!(<i16 as ScalarType>::from_native(-7), -7);
+        assert_eq!(<u16 as ScalarType>::from_native(u32::max_value()), 65535);
+        assert_eq!(
+            <i32 as ScalarType>::from_native(i32::min_value()),
+            i32::min_value()
+        );
+        assert_eq!(<f64 as ScalarType>::from_native(-0.5), -0.5);
+    }
+
+    #[test]
+    fn edit_native_converts_all_components() {
+        let mut value = [1u8, 2, 3];
+        let changed = edit_native(&mut value, |data, components| {
+            assert_eq!(components, 3);
+            let native = unsafe { slice::from_raw_parts_mut(data as *mut u32, 3) };
+            assert_eq!(native, [1, 2, 3]);
+            native[0] = 1000;
+            native[2] = 4;
+            true
+        });
+        assert!(changed);
+        assert_eq!(value, [255, 2, 4]);
+    }
+
+    #[test]
+    fn edit_native_keeps_value_if_unchanged() {
+        let mut value = -5i16;
+        let changed = edit_native(&mut value, |data, components| {
+            assert_eq!(components, 1);
+            unsafe { *(data as *mut i32) = 10 };
+            false
+        });
+        assert!(!changed);
+        assert_eq!(value, -5);
+    }
+}
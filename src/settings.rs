@@ -0,0 +1,356 @@
+use std::fmt::{self, Write};
+use std::sync::{Arc, Mutex};
+
+/// Persists application state in the ini settings together with the window settings.
+///
+/// Entries are stored in sections named `[TypeName][EntryName]`, where the type name is given
+/// when the handler is registered with [`ImGui::add_settings_handler`]. Handlers are owned by
+/// `ImGui`, which can be sent to other threads.
+pub trait SettingsHandler: Send {
+    /// Called when a section of this handler's type is read.
+    ///
+    /// Return false to leave the section to the other handlers of the type. Sections that no
+    /// handler accepts are saved unchanged.
+    fn read_open(&mut self, name: &str) -> bool;
+    /// Called for each non-empty line of the section last accepted by `read_open`.
+    fn read_line(&mut self, line: &str);
+    /// Called when the settings are saved to write all entries.
+    fn write_all(&mut self, out: &mut SettingsWriter);
+}
+
+/// Writes the entries of a [`SettingsHandler`].
+pub struct SettingsWriter<'a> {
+    type_name: &'a str,
+    buf: &'a mut String,
+}
+
+impl<'a> SettingsWriter<'a> {
+    /// Starts a new `[TypeName][name]` section.
+    pub fn entry(&mut self, name: &str) {
+        let _ = writeln!(self.buf, "[{}][{}]", self.type_name, name);
+    }
+    /// Writes a line to the current section.
+    pub fn line(&mut self, line: &str) {
+        self.buf.push_str(line);
+        self.buf.push('\n');
+    }
+    /// Writes a `key=value` line to the current section.
+    pub fn key_value<T: fmt::Display>(&mut self, key: &str, value: T) {
+        let _ = writeln!(self.buf, "{}={}", key, value);
+    }
+}
+
+/// A struct that is persisted as `key=value` lines, see [`ImGui::add_settings`].
+pub trait KeyValueSettings {
+    /// Called for each `key=value` line read from the settings.
+    fn read(&mut self, key: &str, value: &str);
+    /// Writes all values with [`SettingsWriter::key_value`].
+    fn write(&self, out: &mut SettingsWriter);
+}
+
+/// Settings handler for a single value shared with the application.
+pub(crate) struct KeyValueHandler<T> {
+    name: String,
+    value: Arc<Mutex<T>>,
+}
+
+impl<T> KeyValueHandler<T> {
+    pub(crate) fn new(name: &str, value: Arc<Mutex<T>>) -> KeyValueHandler<T> {
+        KeyValueHandler {
+            name: name.to_owned(),
+            value,
+        }
+    }
+}
+
+impl<T: KeyValueSettings + Send> SettingsHandler for KeyValueHandler<T> {
+    fn read_open(&mut self, name: &str) -> bool {
+        name == self.name
+    }
+    fn read_line(&mut self, line: &str) {
+        if let Some(pos) = line.find('=') {
+            self.value
+                .lock()
+                .unwrap()
+                .read(line[..pos].trim(), line[pos + 1..].trim());
+        }
+    }
+    fn write_all(&mut self, out: &mut SettingsWriter) {
+        out.entry(&self.name);
+        self.value.lock().unwrap().write(out);
+        out.line("");
+    }
+}
+
+/// The registered handlers of a context.
+///
+/// cimgui doesn't expose `ImGuiSettingsHandler`, so sections of registered types are split
+/// from the ini data before it is passed to dear imgui, and appended after it is saved.
+#[derive(Default)]
+pub(crate) struct SettingsHandlers {
+    handlers: Vec<(String, Box<dyn SettingsHandler>)>,
+    // Sections of registered types that no handler accepted, written back unchanged
+    unclaimed: String,
+}
+
+/// Where the lines of the current section go while reading.
+#[derive(Copy, Clone)]
+enum Section {
+    /// Not of a registered type, passed to dear imgui
+    Other,
+    /// Accepted by the handler with this index
+    Claimed(usize),
+    /// Of a registered type, but no handler accepted it
+    Unclaimed,
+}
+
+impl SettingsHandlers {
+    pub(crate) fn add(&mut self, type_name: &str, handler: Box<dyn SettingsHandler>) {
+        assert!(
+            !type_name.contains(']'),
+            "Settings type names can't contain ']'"
+        );
+        self.handlers.push((type_name.to_owned(), handler));
+    }
+    pub(crate) fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+    /// Passes the sections of registered types to their handlers and returns the other
+    /// sections.
+    pub(crate) fn read(&mut self, ini: &str) -> String {
+        let mut rest = String::with_capacity(ini.len());
+        self.unclaimed.clear();
+        let mut current = Section::Other;
+        for line in ini.lines() {
+            let trimmed = line.trim();
+            let header = parse_section_header(trimmed);
+            if let Some((type_name, name)) = header {
+                current = self.open_section(type_name, name);
+            }
+            match current {
+                Section::Other => push_line(&mut rest, line),
+                Section::Unclaimed => push_line(&mut self.unclaimed, line),
+                Section::Claimed(index) => {
+                    if header.is_none() && !trimmed.is_empty() {
+                        self.handlers[index].1.read_line(trimmed);
+                    }
+                }
+            }
+        }
+        rest
+    }
+    /// Offers a section to each handler of its type until one accepts it.
+    fn open_section(&mut self, type_name: &str, name: &str) -> Section {
+        let mut registered = false;
+        for (index, (handler_type, handler)) in self.handlers.iter_mut().enumerate() {
+            if handler_type == type_name {
+                if handler.read_open(name) {
+                    return Section::Claimed(index);
+                }
+                registered = true;
+            }
+        }
+        if registered {
+            Section::Unclaimed
+        } else {
+            Section::Other
+        }
+    }
+    /// Appends the sections of all registered handlers.
+    pub(crate) fn write(&mut self, buf: &mut String) {
+        for &mut (ref type_name, ref mut handler) in &mut self.handlers {
+            let mut writer = SettingsWriter { type_name, buf };
+            handler.write_all(&mut writer);
+        }
+        buf.push_str(&self.unclaimed);
+    }
+}
+
+fn push_line(buf: &mut String, line: &str) {
+    buf.push_str(line);
+    buf.push('\n');
+}
+
+/// Parses `[TypeName][EntryName]` like dear imgui does.
+fn parse_section_header(line: &str) -> Option<(&str, &str)> {
+    if !line.starts_with('[') || !line.ends_with(']') {
+        return None;
+    }
+    let inner = &line[1..line.len() - 1];
+    let type_end = inner.find(']')?;
+    let rest = &inner[type_end + 1..];
+    let name_start = rest.find('[')?;
+    Some((&inner[..type_end], &rest[name_start + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Claims the sections with the given names and records their lines.
+    struct Recorder {
+        names: Vec<&'static str>,
+        lines: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Recorder {
+        fn new(names: &[&'static str]) -> (Recorder, Arc<Mutex<Vec<String>>>) {
+            let lines = Arc::new(Mutex::new(Vec::new()));
+            let recorder = Recorder {
+                names: names.to_vec(),
+                lines: lines.clone(),
+            };
+            (recorder, lines)
+        }
+    }
+
+    impl SettingsHandler for Recorder {
+        fn read_open(&mut self, name: &str) -> bool {
+            let claimed = self.names.contains(&name);
+            if claimed {
+                self.lines.lock().unwrap().push(format!("open {}", name));
+            }
+            claimed
+        }
+        fn read_line(&mut self, line: &str) {
+            self.lines.lock().unwrap().push(line.to_owned());
+        }
+        fn write_all(&mut self, out: &mut SettingsWriter) {
+            for name in &self.names {
+                out.entry(name);
+                out.line("Written=1");
+                out.line("");
+            }
+        }
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Panels {
+        show_log: bool,
+        split: f32,
+    }
+
+    impl KeyValueSettings for Panels {
+        fn read(&mut self, key: &str, value: &str) {
+            match key {
+                "ShowLog" => self.show_log = value == "1",
+                "Split" => self.split = value.parse().unwrap_or(0.0),
+                _ => (),
+            }
+        }
+        fn write(&self, out: &mut SettingsWriter) {
+            out.key_value("ShowLog", self.show_log as i32);
+            out.key_value("Split", self.split);
+        }
+    }
+
+    const INI: &str = "\
+[Window][Debug##Default]
+Pos=60,60
+Size=400,400
+
+[MyApp][Panels]
+ShowLog=1
+ Split = 0.25 
+
+[MyApp][Removed]
+Old=1
+
+[Other][Panels]
+Kept=1
+";
+
+    #[test]
+    fn parses_section_headers() {
+        assert_eq!(
+            parse_section_header("[Window][Debug##Default]"),
+            Some(("Window", "Debug##Default"))
+        );
+        assert_eq!(parse_section_header("[Type] [a]b]"), Some(("Type", "a]b")));
+        assert_eq!(parse_section_header("[Type][]"), Some(("Type", "")));
+        assert_eq!(parse_section_header("[Type]"), None);
+        assert_eq!(parse_section_header("Key=[a][b]"), None);
+        assert_eq!(parse_section_header(""), None);
+    }
+
+    #[test]
+    fn sections_are_passed_to_the_handlers_of_their_type() {
+        let mut handlers = SettingsHandlers::default();
+        let (recorder, lines) = Recorder::new(&["Panels"]);
+        handlers.add("MyApp", Box::new(recorder));
+        let rest = handlers.read(INI);
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec!["open Panels", "ShowLog=1", "Split = 0.25"]
+        );
+        assert_eq!(
+            rest,
+            "[Window][Debug##Default]\nPos=60,60\nSize=400,400\n\n[Other][Panels]\nKept=1\n"
+        );
+    }
+
+    #[test]
+    fn sections_are_offered_to_every_handler_of_the_type() {
+        let mut handlers = SettingsHandlers::default();
+        let (first, first_lines) = Recorder::new(&["Removed"]);
+        let (second, second_lines) = Recorder::new(&["Panels"]);
+        handlers.add("MyApp", Box::new(first));
+        handlers.add("MyApp", Box::new(second));
+        handlers.read(INI);
+        assert_eq!(*first_lines.lock().unwrap(), vec!["open Removed", "Old=1"]);
+        assert_eq!(
+            *second_lines.lock().unwrap(),
+            vec!["open Panels", "ShowLog=1", "Split = 0.25"]
+        );
+    }
+
+    #[test]
+    fn unclaimed_sections_are_written_back_unchanged() {
+        let mut handlers = SettingsHandlers::default();
+        let (recorder, _) = Recorder::new(&["Panels"]);
+        handlers.add("MyApp", Box::new(recorder));
+        handlers.read(INI);
+        let mut out = String::new();
+        handlers.write(&mut out);
+        assert_eq!(
+            out,
+            "[MyApp][Panels]\nWritten=1\n\n[MyApp][Removed]\nOld=1\n\n"
+        );
+    }
+
+    #[test]
+    fn key_value_settings_round_trip() {
+        let saved = Arc::new(Mutex::new(Panels {
+            show_log: true,
+            split: 0.75,
+        }));
+        let mut handlers = SettingsHandlers::default();
+        handlers.add("MyApp", Box::new(KeyValueHandler::new("Panels", saved)));
+        let mut ini = String::from("[Window][Main]\nCollapsed=0\n\n");
+        handlers.write(&mut ini);
+
+        let loaded = Arc::new(Mutex::new(Panels::default()));
+        let mut handlers = SettingsHandlers::default();
+        handlers.add(
+            "MyApp",
+            Box::new(KeyValueHandler::new("Panels", loaded.clone())),
+        );
+        let rest = handlers.read(&ini);
+        assert_eq!(rest, "[Window][Main]\nCollapsed=0\n\n");
+        assert_eq!(
+            *loaded.lock().unwrap(),
+            Panels {
+                show_log: true,
+                split: 0.75,
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "can't contain ']'")]
+    fn type_names_cannot_contain_brackets() {
+        let mut handlers = SettingsHandlers::default();
+        let (recorder, _) = Recorder::new(&[]);
+        handlers.add("My]App", Box::new(recorder));
+    }
+}
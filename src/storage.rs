@@ -0,0 +1,126 @@
+use std::marker::PhantomData;
+use std::ptr;
+use sys;
+use sys::{ImGuiID, ImGuiStorage, ImVector};
+
+/// A key-value store for small pieces of widget state, keyed by ids from [`Ui::get_id`].
+///
+/// Obtained with [`Ui::storage`] for the storage of the current window, or from a
+/// [`StateStorage`] owned by the application.
+pub struct Storage<'a> {
+    raw: *mut ImGuiStorage,
+    _phantom: PhantomData<&'a mut ImGuiStorage>,
+}
+
+impl<'a> Storage<'a> {
+    pub(crate) unsafe fn from_ptr(raw: *mut ImGuiStorage) -> Storage<'a> {
+        Storage {
+            raw,
+            _phantom: PhantomData,
+        }
+    }
+    pub fn get_i32(&self, key: ImGuiID, default: i32) -> i32 {
+        unsafe { sys::ImGuiStorage_GetInt(self.raw, key, default) }
+    }
+    pub fn set_i32(&mut self, key: ImGuiID, value: i32) {
+        unsafe { sys::ImGuiStorage_SetInt(self.raw, key, value) };
+    }
+    pub fn get_bool(&self, key: ImGuiID, default: bool) -> bool {
+        unsafe { sys::ImGuiStorage_GetBool(self.raw, key, default) }
+    }
+    pub fn set_bool(&mut self, key: ImGuiID, value: bool) {
+        unsafe { sys::ImGuiStorage_SetBool(self.raw, key, value) };
+    }
+    pub fn get_f32(&self, key: ImGuiID, default: f32) -> f32 {
+        unsafe { sys::ImGuiStorage_GetFloat(self.raw, key, default) }
+    }
+    pub fn set_f32(&mut self, key: ImGuiID, value: f32) {
+        unsafe { sys::ImGuiStorage_SetFloat(self.raw, key, value) };
+    }
+    /// Removes all values.
+    pub fn clear(&mut self) {
+        unsafe { sys::ImGuiStorage_Clear(self.raw) };
+    }
+}
+
+/// A storage owned by the application, which can replace the storage of a window with
+/// [`Ui::with_state_storage`].
+///
+/// Widgets that keep their state in the window storage (e.g. tree nodes) then use this storage
+/// instead, so their state can be kept across windows or reset by the application.
+pub struct StateStorage {
+    raw: Box<ImGuiStorage>,
+}
+
+impl StateStorage {
+    pub fn new() -> StateStorage {
+        StateStorage {
+            // An empty ImVector doesn't own any memory
+            raw: Box::new(ImGuiStorage {
+                data: ImVector {
+                    size: 0,
+                    capacity: 0,
+                    data: ptr::null_mut(),
+                },
+            }),
+        }
+    }
+    pub fn storage(&mut self) -> Storage<'_> {
+        unsafe { Storage::from_ptr(self.raw()) }
+    }
+    pub(crate) fn raw(&mut self) -> *mut ImGuiStorage {
+        &mut *self.raw
+    }
+}
+
+impl Default for StateStorage {
+    fn default() -> StateStorage {
+        StateStorage::new()
+    }
+}
+
+impl Drop for StateStorage {
+    fn drop(&mut self) {
+        // Frees the buffer allocated by dear imgui
+        unsafe { sys::ImGuiStorage_Clear(self.raw()) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_keys_return_the_default() {
+        let mut state = StateStorage::new();
+        let storage = state.storage();
+        assert_eq!(storage.get_i32(1, -1), -1);
+        assert!(storage.get_bool(1, true));
+        assert_eq!(storage.get_f32(1, 0.5), 0.5);
+    }
+
+    #[test]
+    fn values_are_stored_by_key() {
+        let mut state = StateStorage::new();
+        let mut storage = state.storage();
+        storage.set_i32(1, 42);
+        storage.set_bool(2, true);
+        storage.set_f32(3, 1.5);
+        storage.set_i32(1, 43);
+        assert_eq!(storage.get_i32(1, 0), 43);
+        assert!(storage.get_bool(2, false));
+        assert_eq!(storage.get_f32(3, 0.0), 1.5);
+        assert_eq!(storage.get_i32(4, 0), 0);
+
+        storage.clear();
+        assert_eq!(storage.get_i32(1, 0), 0);
+        assert_eq!(storage.get_f32(3, 0.0), 0.0);
+    }
+
+    #[test]
+    fn values_outlive_the_handle() {
+        let mut state = StateStorage::new();
+        state.storage().set_i32(7, 1);
+        assert_eq!(state.storage().get_i32(7, 0), 1);
+    }
+}
@@ -1,4 +1,8 @@
-use crate::ImVec2;
+use std::error::Error;
+use std::fmt::{self, Write};
+
+use crate::sys;
+use crate::{get_style_color_name, ImGuiCol, ImGuiStyle, ImVec2, ImVec4};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum StyleVar {
@@ -20,3 +24,375 @@ pub enum StyleVar {
     GrabMinSize(f32),
     ButtonTextAlign(ImVec2),
 }
+
+/// Built-in color schemes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StylePreset {
+    Dark,
+    Classic,
+    Light,
+}
+
+impl StylePreset {
+    pub(crate) fn apply(self, style: &mut ImGuiStyle) {
+        unsafe {
+            match self {
+                StylePreset::Dark => sys::igStyleColorsDark(style),
+                StylePreset::Classic => sys::igStyleColorsClassic(style),
+                StylePreset::Light => sys::igStyleColorsLight(style),
+            }
+        }
+    }
+}
+
+/// Error returned by [`ImGui::load_style`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StyleParseError {
+    /// Line number, starting from 1
+    pub line: usize,
+    pub kind: StyleParseErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StyleParseErrorKind {
+    /// The line is not a `key = value` pair or a section header.
+    Syntax,
+    /// The section header is not `[colors]`.
+    UnknownSection(String),
+    /// The key is not a style variable or color name.
+    UnknownKey(String),
+    /// The value doesn't have the type of the style variable.
+    InvalidValue(String),
+}
+
+impl fmt::Display for StyleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::StyleParseErrorKind::*;
+        match self.kind {
+            Syntax => write!(f, "Line {}: expected `key = value`", self.line),
+            UnknownSection(ref name) => write!(f, "Line {}: unknown section [{}]", self.line, name),
+            UnknownKey(ref key) => write!(f, "Line {}: unknown key {}", self.line, key),
+            InvalidValue(ref key) => write!(f, "Line {}: invalid value for {}", self.line, key),
+        }
+    }
+}
+
+impl Error for StyleParseError {}
+
+/// A value in the style text format.
+trait StyleValue: Sized {
+    fn write(&self, out: &mut String);
+    fn parse(s: &str) -> Option<Self>;
+}
+
+impl StyleValue for f32 {
+    fn write(&self, out: &mut String) {
+        let _ = write!(out, "{:?}", self);
+    }
+    fn parse(s: &str) -> Option<f32> {
+        s.parse().ok()
+    }
+}
+
+impl StyleValue for bool {
+    fn write(&self, out: &mut String) {
+        let _ = write!(out, "{}", self);
+    }
+    fn parse(s: &str) -> Option<bool> {
+        s.parse().ok()
+    }
+}
+
+fn write_floats(out: &mut String, values: &[f32]) {
+    out.push('[');
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        value.write(out);
+    }
+    out.push(']');
+}
+
+fn parse_floats(s: &str, values: &mut [f32]) -> Option<()> {
+    if !s.starts_with('[') || !s.ends_with(']') {
+        return None;
+    }
+    let mut parts = s[1..s.len() - 1].split(',');
+    for value in values.iter_mut() {
+        *value = f32::parse(parts.next()?.trim())?;
+    }
+    match parts.next() {
+        None => Some(()),
+        Some(_) => None,
+    }
+}
+
+impl StyleValue for ImVec2 {
+    fn write(&self, out: &mut String) {
+        write_floats(out, &[self.x, self.y]);
+    }
+    fn parse(s: &str) -> Option<ImVec2> {
+        let mut values = [0.0; 2];
+        parse_floats(s, &mut values)?;
+        Some(values.into())
+    }
+}
+
+impl StyleValue for ImVec4 {
+    fn write(&self, out: &mut String) {
+        write_floats(out, &[self.x, self.y, self.z, self.w]);
+    }
+    fn parse(s: &str) -> Option<ImVec4> {
+        let mut values = [0.0; 4];
+        parse_floats(s, &mut values)?;
+        Some(values.into())
+    }
+}
+
+macro_rules! style_fields {
+    ($($field:ident),* $(,)*) => {
+        fn write_fields(style: &ImGuiStyle, out: &mut String) {
+            $(
+                out.push_str(concat!(stringify!($field), " = "));
+                style.$field.write(out);
+                out.push('\n');
+            )*
+        }
+
+        /// Returns `None` if the key is unknown, `Some(false)` if the value is invalid.
+        fn read_field(style: &mut ImGuiStyle, key: &str, value: &str) -> Option<bool> {
+            match key {
+                $(
+                    stringify!($field) => Some(match StyleValue::parse(value) {
+                        Some(value) => {
+                            style.$field = value;
+                            true
+                        }
+                        None => false,
+                    }),
+                )*
+                _ => None,
+            }
+        }
+    };
+}
+
+style_fields!(
+    alpha,
+    window_padding,
+    window_rounding,
+    window_border_size,
+    window_min_size,
+    window_title_align,
+    child_rounding,
+    child_border_size,
+    popup_rounding,
+    popup_border_size,
+    frame_padding,
+    frame_rounding,
+    frame_border_size,
+    item_spacing,
+    item_inner_spacing,
+    touch_extra_padding,
+    indent_spacing,
+    columns_min_spacing,
+    scrollbar_size,
+    scrollbar_rounding,
+    grab_min_size,
+    grab_rounding,
+    button_text_align,
+    display_window_padding,
+    display_safe_area_padding,
+    mouse_cursor_scale,
+    anti_aliased_lines,
+    anti_aliased_fill,
+    curve_tessellation_tol,
+);
+
+/// Writes a style as `key = value` lines, followed by a `[colors]` section.
+pub(crate) fn write_style(style: &ImGuiStyle) -> String {
+    let mut out = String::new();
+    write_fields(style, &mut out);
+    out.push_str("\n[colors]\n");
+    for &color in ImGuiCol::VARIANTS.iter() {
+        let _ = write!(out, "{} = ", get_style_color_name(color).to_str());
+        style.colors[color as usize].write(&mut out);
+        out.push('\n');
+    }
+    out
+}
+
+/// Applies the values in the text to the style. Values missing from the text are not changed.
+pub(crate) fn read_style(style: &mut ImGuiStyle, text: &str) -> Result<(), StyleParseError> {
+    let mut in_colors = false;
+    for (index, line) in text.lines().enumerate() {
+        let error = |kind| StyleParseError {
+            line: index + 1,
+            kind,
+        };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            let section = line[1..line.len() - 1].trim();
+            if section != "colors" {
+                return Err(error(StyleParseErrorKind::UnknownSection(
+                    section.to_owned(),
+                )));
+            }
+            in_colors = true;
+            continue;
+        }
+        let pos = line
+            .find('=')
+            .ok_or_else(|| error(StyleParseErrorKind::Syntax))?;
+        let (key, value) = (line[..pos].trim(), line[pos + 1..].trim());
+        let valid = if in_colors {
+            ImGuiCol::VARIANTS
+                .iter()
+                .find(|&&color| get_style_color_name(color).to_str() == key)
+                .map(|&color| match ImVec4::parse(value) {
+                    Some(value) => {
+                        style.colors[color as usize] = value;
+                        true
+                    }
+                    None => false,
+                })
+        } else {
+            read_field(style, key, value)
+        };
+        match valid {
+            Some(true) => (),
+            Some(false) => return Err(error(StyleParseErrorKind::InvalidValue(key.to_owned()))),
+            None => return Err(error(StyleParseErrorKind::UnknownKey(key.to_owned()))),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImGui;
+
+    fn load_error(text: &str) -> StyleParseError {
+        let mut imgui = ImGui::init();
+        let before = imgui.save_style();
+        let error = imgui.load_style(text).unwrap_err();
+        assert_eq!(imgui.save_style(), before, "Style changed on error");
+        error
+    }
+
+    #[test]
+    fn style_round_trip() {
+        let mut imgui = ImGui::init();
+        imgui.apply_style_preset(StylePreset::Light);
+        imgui.scale_style_sizes(2.0);
+        imgui.style_mut().alpha = 0.5;
+        imgui.style_mut().anti_aliased_fill = false;
+        imgui.set_style_color(ImGuiCol::Text, [0.1, 0.2, 0.3, 0.4]);
+        let text = imgui.save_style();
+
+        let mut loaded = ImGui::init();
+        loaded.load_style(&text).unwrap();
+        assert_eq!(loaded.save_style(), text);
+        assert_eq!(loaded.style().alpha, 0.5);
+        assert!(!loaded.style().anti_aliased_fill);
+        assert_eq!(loaded.style().window_padding, imgui.style().window_padding);
+        assert_eq!(
+            loaded.style_color(ImGuiCol::Text),
+            ImVec4::new(0.1, 0.2, 0.3, 0.4)
+        );
+    }
+
+    #[test]
+    fn missing_values_are_not_changed() {
+        let mut imgui = ImGui::init();
+        let padding = imgui.style().window_padding;
+        let text = "# Comment\n\n  alpha = 0.25  \n[colors]\nText = [1.0, 0.0, 0.0, 1.0]\n";
+        imgui.load_style(text).unwrap();
+        assert_eq!(imgui.style().alpha, 0.25);
+        assert_eq!(imgui.style().window_padding, padding);
+        assert_eq!(
+            imgui.style_color(ImGuiCol::Text),
+            ImVec4::new(1.0, 0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_errors() {
+        assert_eq!(
+            load_error("alpha = 1.0\nalpah = 1.0\n"),
+            StyleParseError {
+                line: 2,
+                kind: StyleParseErrorKind::UnknownKey("alpah".to_owned()),
+            }
+        );
+        // Colors are only valid in the colors section
+        assert_eq!(
+            load_error("Text = [1.0, 1.0, 1.0, 1.0]"),
+            StyleParseError {
+                line: 1,
+                kind: StyleParseErrorKind::UnknownKey("Text".to_owned()),
+            }
+        );
+        assert_eq!(
+            load_error("[colors]\nalpha = 1.0"),
+            StyleParseError {
+                line: 2,
+                kind: StyleParseErrorKind::UnknownKey("alpha".to_owned()),
+            }
+        );
+        assert_eq!(
+            load_error("[sizes]"),
+            StyleParseError {
+                line: 1,
+                kind: StyleParseErrorKind::UnknownSection("sizes".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn malformed_lines_are_errors() {
+        assert_eq!(
+            load_error("alpha 1.0"),
+            StyleParseError {
+                line: 1,
+                kind: StyleParseErrorKind::Syntax,
+            }
+        );
+        let invalid = |key: &str| StyleParseErrorKind::InvalidValue(key.to_owned());
+        assert_eq!(load_error("alpha = one").kind, invalid("alpha"));
+        assert_eq!(
+            load_error("anti_aliased_lines = 1").kind,
+            invalid("anti_aliased_lines")
+        );
+        assert_eq!(
+            load_error("window_padding = 1.0, 2.0").kind,
+            invalid("window_padding")
+        );
+        assert_eq!(
+            load_error("window_padding = [1.0]").kind,
+            invalid("window_padding")
+        );
+        assert_eq!(
+            load_error("window_padding = [1.0, 2.0, 3.0]").kind,
+            invalid("window_padding")
+        );
+        assert_eq!(
+            load_error("[colors]\nText = [1.0, 1.0, 1.0]").kind,
+            invalid("Text")
+        );
+    }
+
+    #[test]
+    fn errors_display_the_line() {
+        let error = StyleParseError {
+            line: 3,
+            kind: StyleParseErrorKind::UnknownKey("alpah".to_owned()),
+        };
+        assert_eq!(error.to_string(), "Line 3: unknown key alpah");
+    }
+}
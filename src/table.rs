@@ -0,0 +1,303 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+use sys;
+use sys::ImGuiID;
+
+use super::{ImGuiSelectableFlags, ImStr, ImString, ImVec2, Ui};
+
+/// A column of a [`Table`].
+#[derive(Copy, Clone, Debug)]
+pub struct TableColumn<'p> {
+    label: &'p ImStr,
+    width: Option<f32>,
+    sortable: bool,
+}
+
+impl<'p> TableColumn<'p> {
+    pub fn new(label: &'p ImStr) -> TableColumn<'p> {
+        TableColumn {
+            label,
+            width: None,
+            sortable: false,
+        }
+    }
+    /// Set the initial width of the column (default: the available width is split evenly).
+    ///
+    /// Once the user resizes the column, the stored width is used instead.
+    #[inline]
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+    /// Sort the table by this column when the header is clicked (default: false).
+    #[inline]
+    pub fn sortable(mut self, value: bool) -> Self {
+        self.sortable = value;
+        self
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// The column the user has requested the rows to be sorted by.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SortSpec {
+    pub column: usize,
+    pub direction: SortDirection,
+}
+
+/// Keys of the table state in the window storage, relative to the table id.
+struct StorageKeys {
+    initialized: ImGuiID,
+    sort_column: ImGuiID,
+    sort_descending: ImGuiID,
+}
+
+fn key(name: &[u8]) -> ImGuiID {
+    unsafe { sys::igGetIDStr(name.as_ptr() as *const c_char) }
+}
+
+fn column_width_key(column: usize) -> ImGuiID {
+    unsafe {
+        sys::igPushIDInt(column as c_int);
+        let key = key(b"width\0");
+        sys::igPopID();
+        key
+    }
+}
+
+/// Created by call to [`Ui::table`].
+///
+/// The sort spec and the column widths resized by the user are kept in the state storage of
+/// the current window.
+#[must_use]
+pub struct Table<'ui, 'p> {
+    id: &'p ImStr,
+    columns: &'p [TableColumn<'p>],
+    border: bool,
+    default_sort: Option<SortSpec>,
+    _phantom: PhantomData<&'ui Ui<'ui>>,
+}
+
+impl<'ui, 'p> Table<'ui, 'p> {
+    pub fn new(_: &Ui<'ui>, id: &'p ImStr, columns: &'p [TableColumn<'p>]) -> Self {
+        assert!(!columns.is_empty(), "A table needs at least one column");
+        Table {
+            id,
+            columns,
+            border: true,
+            default_sort: None,
+            _phantom: PhantomData,
+        }
+    }
+    /// Draw borders between the columns, which can be dragged to resize them (default: true).
+    #[inline]
+    pub fn border(mut self, value: bool) -> Self {
+        self.border = value;
+        self
+    }
+    /// Set the sort spec used until a header is clicked (default: unsorted).
+    #[inline]
+    pub fn default_sort(mut self, sort: SortSpec) -> Self {
+        self.default_sort = Some(sort);
+        self
+    }
+    /// Draws the header row, then calls `f` to submit the rows.
+    pub fn build<F: FnOnce(&TableBody)>(self, f: F) {
+        let count = self.columns.len();
+        unsafe {
+            sys::igPushIDStr(self.id.as_ptr());
+            sys::igColumns(count as c_int, self.id.as_ptr(), self.border);
+        }
+        let storage = unsafe { sys::igGetStateStorage() };
+        let keys = StorageKeys {
+            initialized: key(b"initialized\0"),
+            sort_column: key(b"sort_column\0"),
+            sort_descending: key(b"sort_descending\0"),
+        };
+        let initialized = unsafe { sys::ImGuiStorage_GetBool(storage, keys.initialized, false) };
+        if !initialized {
+            self.restore_state(storage, &keys);
+        }
+
+        let mut sort = unsafe {
+            let column = sys::ImGuiStorage_GetInt(storage, keys.sort_column, -1);
+            if column >= 0 && (column as usize) < count {
+                let descending = sys::ImGuiStorage_GetBool(storage, keys.sort_descending, false);
+                Some(SortSpec {
+                    column: column as usize,
+                    direction: if descending {
+                        SortDirection::Descending
+                    } else {
+                        SortDirection::Ascending
+                    },
+                })
+            } else {
+                None
+            }
+        };
+        let clicked = self.header(&mut sort);
+        if clicked {
+            unsafe {
+                let (column, descending) = match sort {
+                    Some(spec) => (
+                        spec.column as c_int,
+                        spec.direction == SortDirection::Descending,
+                    ),
+                    None => (-1, false),
+                };
+                sys::ImGuiStorage_SetInt(storage, keys.sort_column, column);
+                sys::ImGuiStorage_SetBool(storage, keys.sort_descending, descending);
+            }
+        }
+
+        let body = TableBody {
+            columns: count,
+            sort,
+            sort_changed: clicked || !initialized,
+            row: Cell::new(0),
+            _phantom: PhantomData,
+        };
+        f(&body);
+
+        unsafe {
+            for column in 0..count {
+                let width = sys::igGetColumnWidth(column as c_int);
+                sys::ImGuiStorage_SetFloat(storage, column_width_key(column), width);
+            }
+            sys::igColumns(1, ptr::null(), false);
+            sys::igPopID();
+        }
+    }
+    /// Applies the stored widths and the default sort when the table appears for the first time.
+    fn restore_state(&self, storage: *mut sys::ImGuiStorage, keys: &StorageKeys) {
+        // The right edge of the last column is the edge of the window
+        let resizable = self.columns.len() - 1;
+        for (index, column) in self.columns.iter().enumerate().take(resizable) {
+            let stored =
+                unsafe { sys::ImGuiStorage_GetFloat(storage, column_width_key(index), -1.0) };
+            let width = if stored >= 0.0 {
+                Some(stored)
+            } else {
+                column.width
+            };
+            if let Some(width) = width {
+                unsafe { sys::igSetColumnWidth(index as c_int, width) };
+            }
+        }
+        if let Some(sort) = self.default_sort {
+            unsafe {
+                if sys::ImGuiStorage_GetInt(storage, keys.sort_column, -1) < 0 {
+                    sys::ImGuiStorage_SetInt(storage, keys.sort_column, sort.column as c_int);
+                    sys::ImGuiStorage_SetBool(
+                        storage,
+                        keys.sort_descending,
+                        sort.direction == SortDirection::Descending,
+                    );
+                }
+            }
+        }
+        unsafe { sys::ImGuiStorage_SetBool(storage, keys.initialized, true) };
+    }
+    /// Draws the header row. Returns true if a header was clicked.
+    fn header(&self, sort: &mut Option<SortSpec>) -> bool {
+        let mut changed = false;
+        for (index, column) in self.columns.iter().enumerate() {
+            if column.sortable {
+                let indicator = match *sort {
+                    Some(spec) if spec.column == index => match spec.direction {
+                        SortDirection::Ascending => " ^",
+                        SortDirection::Descending => " v",
+                    },
+                    _ => "",
+                };
+                // "###" keeps the id stable when the indicator changes
+                let label = ImString::new(format!(
+                    "{}{}###column{}",
+                    column.label.to_str(),
+                    indicator,
+                    index
+                ));
+                let clicked = unsafe {
+                    sys::igSelectable(
+                        label.as_ptr(),
+                        false,
+                        ImGuiSelectableFlags::empty(),
+                        ImVec2::new(0.0, 0.0),
+                    )
+                };
+                if clicked {
+                    let direction = match *sort {
+                        Some(SortSpec {
+                            column,
+                            direction: SortDirection::Ascending,
+                        }) if column == index => SortDirection::Descending,
+                        _ => SortDirection::Ascending,
+                    };
+                    *sort = Some(SortSpec {
+                        column: index,
+                        direction,
+                    });
+                    changed = true;
+                }
+            } else {
+                unsafe { sys::igTextUnformatted(column.label.as_ptr(), ptr::null()) };
+            }
+            unsafe { sys::igNextColumn() };
+        }
+        unsafe { sys::igSeparator() };
+        changed
+    }
+}
+
+/// Passed to the closure given to [`Table::build`].
+pub struct TableBody<'ui> {
+    columns: usize,
+    sort: Option<SortSpec>,
+    sort_changed: bool,
+    row: Cell<c_int>,
+    _phantom: PhantomData<&'ui Ui<'ui>>,
+}
+
+impl<'ui> TableBody<'ui> {
+    /// The column and direction the rows should be sorted by, if any.
+    pub fn sort_spec(&self) -> Option<SortSpec> {
+        self.sort
+    }
+    /// Returns true if the rows need to be sorted again, i.e. when the table appears or a header
+    /// was clicked.
+    pub fn sort_spec_changed(&self) -> bool {
+        self.sort_changed
+    }
+    /// Submits a row. The first cell is a selectable with the given label that spans the whole
+    /// row, the other cells are submitted by `f`, which is called with each column index.
+    ///
+    /// Returns true if the row was clicked.
+    pub fn row<F: FnMut(usize)>(&self, label: &ImStr, selected: bool, mut f: F) -> bool {
+        let row = self.row.get();
+        self.row.set(row + 1);
+        unsafe {
+            // Rows may have the same label
+            sys::igPushIDInt(row);
+            let clicked = sys::igSelectable(
+                label.as_ptr(),
+                selected,
+                ImGuiSelectableFlags::SpanAllColumns,
+                ImVec2::new(0.0, 0.0),
+            );
+            sys::igNextColumn();
+            for column in 1..self.columns {
+                f(column);
+                sys::igNextColumn();
+            }
+            sys::igPopID();
+            clicked
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+use sys;
+use sys::{ImGuiTextFilter, ImVector};
+
+use super::{ImStr, Ui};
+
+/// A search box that matches text against a comma-separated list of terms.
+///
+/// Text passes if it contains any of the terms. Terms starting with `-` exclude text that
+/// contains them. Terms are checked in order, so exclusions go first, e.g. `"-radio,button"`
+/// matches "button" but not "radio button".
+///
+/// # Example
+/// ```rust,no_run
+/// # use imgui::*;
+/// # let mut imgui = ImGui::init();
+/// # let ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+/// let mut filter = TextFilter::new();
+/// filter.draw(&ui, im_str!("Search"), 0.0);
+/// for name in &["apple", "banana", "cherry"] {
+///     if filter.pass_filter(name) {
+///         ui.text(name);
+///     }
+/// }
+/// ```
+pub struct TextFilter {
+    // The filter ranges point into the input buffer, so it must not move
+    raw: Box<ImGuiTextFilter>,
+}
+
+impl TextFilter {
+    pub fn new() -> TextFilter {
+        TextFilter {
+            raw: Box::new(ImGuiTextFilter {
+                input_buf: [0; 256],
+                filters: ImVector {
+                    size: 0,
+                    capacity: 0,
+                    data: ptr::null_mut(),
+                },
+                count_grep: 0,
+            }),
+        }
+    }
+    /// Creates a filter with the given initial text.
+    pub fn with_filter(filter: &str) -> TextFilter {
+        let mut text_filter = TextFilter::new();
+        text_filter.set_filter(filter);
+        text_filter
+    }
+    /// Draws the search box. A width of 0.0 uses the default item width.
+    ///
+    /// Returns true if the filter was changed.
+    pub fn draw(&mut self, _: &Ui, label: &ImStr, width: f32) -> bool {
+        unsafe { sys::ImGuiTextFilter_Draw(self.raw(), label.as_ptr(), width) }
+    }
+    /// Returns true if `text` matches the filter, or if the filter is empty.
+    pub fn pass_filter(&self, text: &str) -> bool {
+        let start = text.as_ptr() as *const c_char;
+        unsafe {
+            let end = start.add(text.len());
+            // PassFilter doesn't modify the filter
+            let raw = &*self.raw as *const ImGuiTextFilter as *mut ImGuiTextFilter;
+            sys::ImGuiTextFilter_PassFilter(raw, start, end)
+        }
+    }
+    /// Returns true if the filter is not empty.
+    pub fn is_active(&self) -> bool {
+        self.raw.count_grep > 0
+    }
+    /// The text entered in the search box.
+    pub fn filter(&self) -> &str {
+        let text = unsafe { CStr::from_ptr(self.raw.input_buf.as_ptr()) };
+        text.to_str().unwrap_or("")
+    }
+    /// Replaces the filter text. Text longer than the buffer of dear imgui (255 bytes) is
+    /// truncated.
+    pub fn set_filter(&mut self, filter: &str) {
+        let mut len = filter.len().min(self.raw.input_buf.len() - 1);
+        while !filter.is_char_boundary(len) {
+            len -= 1;
+        }
+        for (dst, &src) in self.raw.input_buf.iter_mut().zip(&filter.as_bytes()[..len]) {
+            *dst = src as c_char;
+        }
+        self.raw.input_buf[len] = 0;
+        unsafe { sys::ImGuiTextFilter_Build(self.raw()) };
+    }
+    pub fn clear(&mut self) {
+        unsafe { sys::ImGuiTextFilter_Clear(self.raw()) };
+    }
+    fn raw(&mut self) -> *mut ImGuiTextFilter {
+        &mut *self.raw
+    }
+}
+
+impl Default for TextFilter {
+    fn default() -> TextFilter {
+        TextFilter::new()
+    }
+}
+
+impl Drop for TextFilter {
+    fn drop(&mut self) {
+        // cimgui doesn't expose the destructor, so free the ranges allocated by dear imgui
+        if !self.raw.filters.data.is_null() {
+            unsafe { sys::igMemFree(self.raw.filters.data as *mut c_void) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_filter_passes_everything() {
+        let filter = TextFilter::new();
+        assert!(!filter.is_active());
+        assert_eq!(filter.filter(), "");
+        assert!(filter.pass_filter("anything"));
+        assert!(filter.pass_filter(""));
+    }
+
+    #[test]
+    fn terms_include_and_exclude() {
+        let filter = TextFilter::with_filter("-radio, button");
+        assert!(filter.is_active());
+        assert_eq!(filter.filter(), "-radio, button");
+        assert!(filter.pass_filter("button"));
+        assert!(filter.pass_filter("Small button"));
+        assert!(!filter.pass_filter("radio button"));
+        assert!(!filter.pass_filter("checkbox"));
+
+        let filter = TextFilter::with_filter("-radio");
+        assert!(filter.pass_filter("checkbox"));
+        assert!(!filter.pass_filter("radio"));
+
+        // The first matching term decides
+        let filter = TextFilter::with_filter("button,-radio");
+        assert!(filter.pass_filter("radio button"));
+    }
+
+    #[test]
+    fn clear_removes_the_filter() {
+        let mut filter = TextFilter::with_filter("button");
+        filter.clear();
+        assert!(!filter.is_active());
+        assert_eq!(filter.filter(), "");
+        assert!(filter.pass_filter("checkbox"));
+
+        filter.set_filter("check");
+        assert!(!filter.pass_filter("button"));
+    }
+
+    #[test]
+    fn long_filters_are_truncated_at_a_char_boundary() {
+        let filter = TextFilter::with_filter(&"a".repeat(300));
+        assert_eq!(filter.filter().len(), 255);
+
+        // The 2-byte character would end at byte 256
+        let text = format!("{}é", "a".repeat(254));
+        let filter = TextFilter::with_filter(&text);
+        assert_eq!(filter.filter(), &text[..254]);
+
+        let text = format!("{}é", "a".repeat(253));
+        let filter = TextFilter::with_filter(&text);
+        assert_eq!(filter.filter(), text);
+    }
+}
@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 use sys;
 
-use super::{ImGuiCond, ImGuiTreeNodeFlags, ImStr, Ui};
+use super::{ImGuiCond, ImGuiTreeNodeFlags, ImStr, TextFilter, Ui};
 
 #[must_use]
 pub struct TreeNode<'ui, 'p> {
@@ -10,6 +10,7 @@ pub struct TreeNode<'ui, 'p> {
     opened: bool,
     opened_cond: ImGuiCond,
     flags: ImGuiTreeNodeFlags,
+    filter: Option<&'p TextFilter>,
     _phantom: PhantomData<&'ui Ui<'ui>>,
 }
 
@@ -21,6 +22,7 @@ impl<'ui, 'p> TreeNode<'ui, 'p> {
             opened: false,
             opened_cond: ImGuiCond::empty(),
             flags: ImGuiTreeNodeFlags::empty(),
+            filter: None,
             _phantom: PhantomData,
         }
     }
@@ -95,7 +97,21 @@ impl<'ui, 'p> TreeNode<'ui, 'p> {
         self.flags.set(ImGuiTreeNodeFlags::FramePadding, value);
         self
     }
+    /// Skip the node and its children if its label doesn't pass `filter`.
+    #[inline]
+    pub fn filter(mut self, filter: &'p TextFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
     pub fn build<F: FnOnce()>(self, f: F) {
+        let label = self.label.unwrap_or(self.id);
+        if let Some(filter) = self.filter {
+            // Only the part before "##" is displayed
+            let text = label.to_str().split("##").next().unwrap_or("");
+            if !filter.pass_filter(text) {
+                return;
+            }
+        }
         let render = unsafe {
             if !self.opened_cond.is_empty() {
                 sys::igSetNextTreeNodeOpen(self.opened, self.opened_cond);
@@ -104,7 +120,7 @@ impl<'ui, 'p> TreeNode<'ui, 'p> {
                 self.id.as_ptr(),
                 self.flags,
                 super::fmt_ptr(),
-                self.label.unwrap_or(self.id).as_ptr(),
+                label.as_ptr(),
             )
         };
         if render {
@@ -1,8 +1,36 @@
 use std::marker::PhantomData;
+use std::os::raw::c_void;
 use std::ptr;
 use sys;
 
-use super::{ImGuiCond, ImGuiWindowFlags, ImStr, Ui};
+use super::{abort_on_panic, ImGuiCond, ImGuiWindowFlags, ImStr, ImVec2, Ui};
+
+/// Passed to the size constraint callback of a [`Window`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SizeCallbackData {
+    /// Current window position
+    pub pos: (f32, f32),
+    /// Current window size
+    pub current_size: (f32, f32),
+    /// Size requested by the user or by auto-resizing, which can be modified by the callback
+    pub desired_size: (f32, f32),
+}
+
+type SizeCallback<'p> = Box<dyn FnMut(&mut SizeCallbackData) + 'p>;
+
+extern "C" fn size_callback(data: *mut sys::ImGuiSizeCallbackData) {
+    abort_on_panic(|| {
+        let data = unsafe { &mut *data };
+        let callback = unsafe { &mut *(data.user_data as *mut SizeCallback) };
+        let mut callback_data = SizeCallbackData {
+            pos: data.pos.into(),
+            current_size: data.current_size.into(),
+            desired_size: data.desired_size.into(),
+        };
+        callback(&mut callback_data);
+        data.desired_size = callback_data.desired_size.into();
+    })
+}
 
 #[must_use]
 pub struct Window<'ui, 'p> {
@@ -14,6 +42,13 @@ pub struct Window<'ui, 'p> {
     name: &'p ImStr,
     opened: Option<&'p mut bool>,
     flags: ImGuiWindowFlags,
+    size_constraints: Option<(ImVec2, ImVec2)>,
+    size_callback: Option<SizeCallback<'p>>,
+    content_size: Option<ImVec2>,
+    collapsed: bool,
+    collapsed_cond: ImGuiCond,
+    focused: bool,
+    bg_alpha: Option<f32>,
     _phantom: PhantomData<&'ui Ui<'ui>>,
 }
 
@@ -28,6 +63,13 @@ impl<'ui, 'p> Window<'ui, 'p> {
             name,
             opened: None,
             flags: ImGuiWindowFlags::empty(),
+            size_constraints: None,
+            size_callback: None,
+            content_size: None,
+            collapsed: false,
+            collapsed_cond: ImGuiCond::empty(),
+            focused: false,
+            bg_alpha: None,
             _phantom: PhantomData,
         }
     }
@@ -48,6 +90,67 @@ impl<'ui, 'p> Window<'ui, 'p> {
         self.size_cond = cond;
         self
     }
+    /// Limit the size of the window. Use -1.0 to keep the current size on an axis, and
+    /// `f32::MAX` for no maximum.
+    #[inline]
+    pub fn size_constraints(mut self, min: (f32, f32), max: (f32, f32)) -> Self {
+        self.size_constraints = Some((min.into(), max.into()));
+        self
+    }
+    /// Like `size_constraints`, and calls `callback` to adjust the desired size within them,
+    /// e.g. to keep the aspect ratio.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # let mut imgui = ImGui::init();
+    /// # let ui = imgui.frame(FrameSize::new(100.0, 100.0, 1.0), 0.1);
+    /// ui.window(im_str!("Square"))
+    ///     .size_constraints_callback((0.0, 0.0), (::std::f32::MAX, ::std::f32::MAX), |data| {
+    ///         let side = data.desired_size.0.max(data.desired_size.1);
+    ///         data.desired_size = (side, side);
+    ///     })
+    ///     .build(|| {});
+    /// ```
+    #[inline]
+    pub fn size_constraints_callback<F>(
+        mut self,
+        min: (f32, f32),
+        max: (f32, f32),
+        callback: F,
+    ) -> Self
+    where
+        F: FnMut(&mut SizeCallbackData) + 'p,
+    {
+        self.size_constraints = Some((min.into(), max.into()));
+        self.size_callback = Some(Box::new(callback));
+        self
+    }
+    /// Set the size of the window contents, excluding padding (default: the size of the
+    /// submitted items).
+    #[inline]
+    pub fn content_size(mut self, size: (f32, f32)) -> Self {
+        self.content_size = Some(size.into());
+        self
+    }
+    #[inline]
+    pub fn collapsed(mut self, value: bool, cond: ImGuiCond) -> Self {
+        self.collapsed = value;
+        self.collapsed_cond = cond;
+        self
+    }
+    /// Focus the window and bring it to the front.
+    #[inline]
+    pub fn focused(mut self, value: bool) -> Self {
+        self.focused = value;
+        self
+    }
+    /// Set the alpha of the window background (default: taken from the style).
+    #[inline]
+    pub fn bg_alpha(mut self, value: f32) -> Self {
+        self.bg_alpha = Some(value);
+        self
+    }
     #[inline]
     pub fn opened(mut self, opened: &'p mut bool) -> Self {
         self.opened = Some(opened);
@@ -142,8 +245,31 @@ impl<'ui, 'p> Window<'ui, 'p> {
             .set(ImGuiWindowFlags::AlwaysUseWindowPadding, value);
         self
     }
-    pub fn build<F: FnOnce()>(self, f: F) {
+    pub fn build<F: FnOnce()>(mut self, f: F) {
         let render = unsafe {
+            if let Some((min, max)) = self.size_constraints {
+                // The callback is only called by igBegin
+                let (callback, user_data) = match self.size_callback {
+                    Some(ref mut callback) => (
+                        Some(size_callback as extern "C" fn(*mut sys::ImGuiSizeCallbackData)),
+                        callback as *mut SizeCallback as *mut c_void,
+                    ),
+                    None => (None, ptr::null_mut()),
+                };
+                sys::igSetNextWindowSizeConstraints(min, max, callback, user_data);
+            }
+            if let Some(content_size) = self.content_size {
+                sys::igSetNextWindowContentSize(content_size);
+            }
+            if !self.collapsed_cond.is_empty() {
+                sys::igSetNextWindowCollapsed(self.collapsed, self.collapsed_cond);
+            }
+            if self.focused {
+                sys::igSetNextWindowFocus();
+            }
+            if let Some(bg_alpha) = self.bg_alpha {
+                sys::igSetNextWindowBgAlpha(bg_alpha);
+            }
             if !self.pos_cond.is_empty() {
                 sys::igSetNextWindowPos(self.pos.into(), self.pos_cond, self.pos_pivot.into());
             }
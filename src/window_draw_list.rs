@@ -1,9 +1,12 @@
 use sys;
-use sys::{ImDrawCornerFlags, ImDrawList, ImU32};
+use sys::{ImDrawCornerFlags, ImDrawIdx, ImDrawList, ImDrawVert, ImU32};
 
-use super::{ImVec2, ImVec4, Ui};
+use super::draw_callback;
+use super::{ImTexture, ImVec2, ImVec4, Ui};
 
+use std::any::Any;
 use std::marker::PhantomData;
+use std::os::raw::{c_int, c_void};
 
 /// Wrap `ImU32` (a type typically used by ImGui to store packed colors)
 /// This type is used to represent the color of drawing primitives in ImGui's
@@ -59,20 +62,28 @@ impl From<(f32, f32, f32)> for ImColor {
 
 /// Object implementing the custom draw API.
 ///
-/// Called from [`Ui::get_window_draw_list`]. No more than one instance of this
-/// structure can live in a program at the same time.
+/// Called from [`Ui::get_window_draw_list`] to draw in the current window,
+/// or from [`Ui::get_overlay_draw_list`] to draw on top of all windows.
+/// No more than one instance of each kind of this structure can live in a
+/// program at the same time.
 /// The program will panic on creating a second instance.
 pub struct WindowDrawList<'ui> {
     draw_list: *mut ImDrawList,
+    overlay: bool,
     _phantom: PhantomData<&'ui Ui<'ui>>,
 }
 
 static mut WINDOW_DRAW_LIST_LOADED: bool = false;
+static mut OVERLAY_DRAW_LIST_LOADED: bool = false;
 
 impl<'ui> Drop for WindowDrawList<'ui> {
     fn drop(&mut self) {
         unsafe {
-            WINDOW_DRAW_LIST_LOADED = false;
+            if self.overlay {
+                OVERLAY_DRAW_LIST_LOADED = false;
+            } else {
+                WINDOW_DRAW_LIST_LOADED = false;
+            }
         }
     }
 }
@@ -87,6 +98,21 @@ impl<'ui> WindowDrawList<'ui> {
         }
         Self {
             draw_list: unsafe { sys::igGetWindowDrawList() },
+            overlay: false,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn overlay(_: &Ui<'ui>) -> Self {
+        unsafe {
+            if OVERLAY_DRAW_LIST_LOADED {
+                panic!("The overlay draw list is already loaded! You can only load one instance of it!")
+            }
+            OVERLAY_DRAW_LIST_LOADED = true;
+        }
+        Self {
+            draw_list: unsafe { sys::igGetOverlayDrawList() },
+            overlay: true,
             _phantom: PhantomData,
         }
     }
@@ -96,6 +122,13 @@ impl<'ui> WindowDrawList<'ui> {
     /// are then drawn in the increasing order of their channel number, and not
     /// in the order they were called.
     ///
+    /// The clipping rectangles and textures pushed with [`with_clip_rect`]
+    /// and [`with_texture`] apply to the channel which is current when
+    /// drawing, so they can be pushed inside or outside of the closure.
+    ///
+    /// [`with_clip_rect`]: WindowDrawList::with_clip_rect
+    /// [`with_texture`]: WindowDrawList::with_texture
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -116,7 +149,18 @@ impl<'ui> WindowDrawList<'ui> {
             draw_list: self,
             channels_count,
         });
-        unsafe { sys::ImDrawList_ChannelsMerge(self.draw_list) };
+        unsafe {
+            sys::ImDrawList_ChannelsMerge(self.draw_list);
+            self.update_current_cmd();
+        }
+    }
+
+    // The current command of a channel keeps the clipping rectangle and
+    // texture of the last time the channel was current, so update them with
+    // the top of the stacks, which are shared by all channels
+    unsafe fn update_current_cmd(&self) {
+        sys::ImDrawList_UpdateClipRect(self.draw_list);
+        sys::ImDrawList_UpdateTextureID(self.draw_list);
     }
 }
 
@@ -140,8 +184,9 @@ impl<'ui> ChannelsSplit<'ui> {
             self.channels_count
         );
         unsafe {
-            sys::ImDrawList_ChannelsSetCurrent(self.draw_list.draw_list, channel_index as i32)
-        };
+            sys::ImDrawList_ChannelsSetCurrent(self.draw_list.draw_list, channel_index as i32);
+            self.draw_list.update_current_cmd();
+        }
     }
 }
 
@@ -260,6 +305,120 @@ impl<'ui> WindowDrawList<'ui> {
         BezierCurve::new(self, pos0, cp0, cp1, pos1, color)
     }
 
+    /// Returns a path with color `color`, to be built from lines, arcs, curves and
+    /// rectangles.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// fn gauge(ui: &Ui, value: f32) {
+    ///     use std::f32::consts::PI;
+    ///     let draw_list = ui.get_window_draw_list();
+    ///     let center = (50.0, 50.0);
+    ///     draw_list
+    ///         .path([1.0, 1.0, 1.0])
+    ///         .arc_to(center, 40.0, PI, PI + value * PI, 32)
+    ///         .thickness(4.0)
+    ///         .build();
+    /// }
+    /// ```
+    pub fn path<C>(&'ui self, color: C) -> Path<'ui>
+    where
+        C: Into<ImColor>,
+    {
+        Path::new(self, color)
+    }
+
+    /// Returns a line through all `points` with color `color`.
+    pub fn add_polyline<I, P, C>(&'ui self, points: I, color: C) -> Polyline<'ui>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<ImVec2>,
+        C: Into<ImColor>,
+    {
+        Polyline::new(self, points, color)
+    }
+
+    /// Returns a quadrilateral with the given 4 vertices and color `c`.
+    pub fn add_quad<P1, P2, P3, P4, C>(&'ui self, p1: P1, p2: P2, p3: P3, p4: P4, c: C) -> Quad<'ui>
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        P3: Into<ImVec2>,
+        P4: Into<ImVec2>,
+        C: Into<ImColor>,
+    {
+        Quad::new(self, p1, p2, p3, p4, c)
+    }
+
+    /// Returns an image whose upper-left corner is at point `p1` and
+    /// lower-right corner is at point `p2`.
+    pub fn add_image<P1, P2>(&'ui self, texture_id: ImTexture, p1: P1, p2: P2) -> DrawImage<'ui>
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+    {
+        DrawImage::new(self, texture_id, p1, p2)
+    }
+
+    /// Returns an image mapped on the quadrilateral with the given 4
+    /// vertices, e.g. to draw a rotated image.
+    pub fn add_image_quad<P1, P2, P3, P4>(
+        &'ui self,
+        texture_id: ImTexture,
+        p1: P1,
+        p2: P2,
+        p3: P3,
+        p4: P4,
+    ) -> DrawImageQuad<'ui>
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        P3: Into<ImVec2>,
+        P4: Into<ImVec2>,
+    {
+        DrawImageQuad::new(self, texture_id, p1, p2, p3, p4)
+    }
+
+    /// Reserve `idx_count` indices and `vtx_count` vertices, written
+    /// directly with the returned [`PrimWriter`]. This is much faster than
+    /// the shape builders when drawing a large number of vertices.
+    ///
+    /// Panic if `idx_count` is not a multiple of 3, or if the vertices
+    /// overflow the 16-bit indices of the draw list.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// fn waveform(ui: &Ui, samples: &[f32]) {
+    ///     let draw_list = ui.get_window_draw_list();
+    ///     let mut writer = draw_list.prim_reserve(6 * samples.len(), 4 * samples.len());
+    ///     let uv = writer.tex_uv_white_pixel();
+    ///     for (i, sample) in samples.iter().enumerate() {
+    ///         let x = i as f32;
+    ///         writer.rect_uv((x, 50.0), (x + 1.0, 50.0 - sample * 50.0), uv, uv, [1.0, 1.0, 1.0]);
+    ///     }
+    /// }
+    /// ```
+    pub fn prim_reserve(&'ui self, idx_count: usize, vtx_count: usize) -> PrimWriter<'ui> {
+        PrimWriter::new(self, None, idx_count, vtx_count)
+    }
+
+    /// Reserve `idx_count` indices and `vtx_count` vertices drawn with the
+    /// texture `texture_id`, see [`prim_reserve`].
+    ///
+    /// [`prim_reserve`]: WindowDrawList::prim_reserve
+    pub fn prim_reserve_textured(
+        &'ui self,
+        texture_id: ImTexture,
+        idx_count: usize,
+        vtx_count: usize,
+    ) -> PrimWriter<'ui> {
+        PrimWriter::new(self, Some(texture_id), idx_count, vtx_count)
+    }
+
     /// Push a clipping rectangle on the stack, run `f` and pop it.
     ///
     /// Clip all drawings done within the closure `f` in the given
@@ -290,6 +449,83 @@ impl<'ui> WindowDrawList<'ui> {
         f();
         unsafe { sys::ImDrawList_PopClipRect(self.draw_list) }
     }
+
+    /// Push a clipping rectangle covering the whole screen on the stack, run
+    /// `f` and pop it.
+    pub fn with_clip_rect_full_screen<F: FnOnce()>(&self, f: F) {
+        unsafe { sys::ImDrawList_PushClipRectFullScreen(self.draw_list) }
+        f();
+        unsafe { sys::ImDrawList_PopClipRect(self.draw_list) }
+    }
+
+    /// Get the upper-left corner of the current clipping rectangle.
+    pub fn get_clip_rect_min(&self) -> (f32, f32) {
+        let min = unsafe { sys::ImDrawList_GetClipRectMin_nonUDT2(self.draw_list) };
+        min.into()
+    }
+
+    /// Get the lower-right corner of the current clipping rectangle.
+    pub fn get_clip_rect_max(&self) -> (f32, f32) {
+        let max = unsafe { sys::ImDrawList_GetClipRectMax_nonUDT2(self.draw_list) };
+        max.into()
+    }
+
+    /// Push a texture on the stack, run `f` and pop it.
+    ///
+    /// Primitives drawn within the closure `f` use the texture, e.g. the
+    /// vertices written with [`prim_reserve`] or rectangles with texture
+    /// coordinates.
+    ///
+    /// [`prim_reserve`]: WindowDrawList::prim_reserve
+    pub fn with_texture<F: FnOnce()>(&self, texture_id: ImTexture, f: F) {
+        unsafe { sys::ImDrawList_PushTextureID(self.draw_list, texture_ptr(texture_id)) }
+        f();
+        unsafe { sys::ImDrawList_PopTextureID(self.draw_list) }
+    }
+
+    /// Add a callback, called by the renderer when it reaches this point of
+    /// the draw list, e.g. to draw a 3D viewport with the graphics API.
+    ///
+    /// The callback receives the render target of the renderer and the
+    /// current clipping rectangle in framebuffer coordinates. It is only
+    /// called if the render target is a `T`: the `CallbackTarget` for glium,
+    /// the `Encoder` for gfx and the `RgbaImage` for the software renderer.
+    ///
+    /// The callback is kept by the context until the next frame starts, and
+    /// the context can be sent to another thread in the meantime, so it must
+    /// be `Send`.
+    ///
+    /// Call [`add_reset_render_state`] afterwards if the callback changes
+    /// state that the renderer relies on.
+    ///
+    /// [`add_reset_render_state`]: WindowDrawList::add_reset_render_state
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use imgui::*;
+    /// # struct Frame;
+    /// fn viewport(ui: &Ui) {
+    ///     let draw_list = ui.get_window_draw_list();
+    ///     draw_list.add_callback(|frame: &mut Frame, clip_rect| {
+    ///         // ... Draw the scene in clip_rect
+    ///     });
+    ///     draw_list.add_reset_render_state();
+    /// }
+    /// ```
+    pub fn add_callback<T, F>(&self, f: F)
+    where
+        T: Any,
+        F: FnMut(&mut T, ImVec4) + Send + 'static,
+    {
+        draw_callback::add_callback(self.draw_list, f);
+    }
+
+    /// Ask the renderer to restore its render state at this point of the draw
+    /// list, e.g. after a callback.
+    pub fn add_reset_render_state(&self) {
+        draw_callback::add_reset_render_state(self.draw_list);
+    }
 }
 
 /// Represents a line about to be drawn
@@ -658,3 +894,766 @@ impl<'ui> BezierCurve<'ui> {
         }
     }
 }
+
+/// Represents a path about to be drawn
+#[must_use = "should call .build() to draw the object"]
+pub struct Path<'ui> {
+    segments: Vec<PathSegment>,
+    color: ImColor,
+    thickness: f32,
+    closed: bool,
+    filled: bool,
+    draw_list: &'ui WindowDrawList<'ui>,
+}
+
+/// A part of a [`Path`], added to the draw list when the path is built.
+enum PathSegment {
+    LineTo(ImVec2),
+    ArcTo {
+        center: ImVec2,
+        radius: f32,
+        a_min: f32,
+        a_max: f32,
+        num_segments: c_int,
+    },
+    BezierCurveTo {
+        cp0: ImVec2,
+        cp1: ImVec2,
+        pos1: ImVec2,
+        num_segments: c_int,
+    },
+    Rect {
+        p1: ImVec2,
+        p2: ImVec2,
+        rounding: f32,
+    },
+}
+
+impl<'ui> Path<'ui> {
+    fn new<C>(draw_list: &'ui WindowDrawList, color: C) -> Self
+    where
+        C: Into<ImColor>,
+    {
+        Self {
+            segments: Vec::new(),
+            color: color.into(),
+            thickness: 1.0,
+            closed: false,
+            filled: false,
+            draw_list,
+        }
+    }
+
+    /// Add a straight line to point `pos`.
+    pub fn line_to<P>(mut self, pos: P) -> Self
+    where
+        P: Into<ImVec2>,
+    {
+        self.segments.push(PathSegment::LineTo(pos.into()));
+        self
+    }
+
+    /// Add an arc of the circle with the given `center` and `radius`, from
+    /// angle `a_min` to `a_max` in radians.
+    pub fn arc_to<P>(
+        mut self,
+        center: P,
+        radius: f32,
+        a_min: f32,
+        a_max: f32,
+        num_segments: u32,
+    ) -> Self
+    where
+        P: Into<ImVec2>,
+    {
+        self.segments.push(PathSegment::ArcTo {
+            center: center.into(),
+            radius,
+            a_min,
+            a_max,
+            num_segments: num_segments as c_int,
+        });
+        self
+    }
+
+    /// Add a Bezier curve from the last point to `pos1`, whose curvature is
+    /// defined by `cp0` and `cp1`. If `num_segments` is 0, the curve is
+    /// auto-tessalated.
+    pub fn bezier_curve_to<P1, P2, P3>(
+        mut self,
+        cp0: P1,
+        cp1: P2,
+        pos1: P3,
+        num_segments: u32,
+    ) -> Self
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        P3: Into<ImVec2>,
+    {
+        self.segments.push(PathSegment::BezierCurveTo {
+            cp0: cp0.into(),
+            cp1: cp1.into(),
+            pos1: pos1.into(),
+            num_segments: num_segments as c_int,
+        });
+        self
+    }
+
+    /// Add the outline of a rectangle with all corners rounded by `rounding`.
+    pub fn rect<P1, P2>(mut self, p1: P1, p2: P2, rounding: f32) -> Self
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+    {
+        self.segments.push(PathSegment::Rect {
+            p1: p1.into(),
+            p2: p2.into(),
+            rounding,
+        });
+        self
+    }
+
+    /// Set path's thickness (default to 1.0 pixel)
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Set to `true` to connect the last point to the first one (default to `false`).
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// Set to `true` to fill the path, which must be convex (default to `false`).
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Draw the path on the window.
+    pub fn build(self) {
+        let draw_list = self.draw_list.draw_list;
+        unsafe {
+            sys::ImDrawList_PathClear(draw_list);
+            for segment in &self.segments {
+                match *segment {
+                    PathSegment::LineTo(pos) => sys::ImDrawList_PathLineTo(draw_list, pos),
+                    PathSegment::ArcTo {
+                        center,
+                        radius,
+                        a_min,
+                        a_max,
+                        num_segments,
+                    } => sys::ImDrawList_PathArcTo(
+                        draw_list,
+                        center,
+                        radius,
+                        a_min,
+                        a_max,
+                        num_segments,
+                    ),
+                    PathSegment::BezierCurveTo {
+                        cp0,
+                        cp1,
+                        pos1,
+                        num_segments,
+                    } => sys::ImDrawList_PathBezierCurveTo(draw_list, cp0, cp1, pos1, num_segments),
+                    PathSegment::Rect { p1, p2, rounding } => sys::ImDrawList_PathRect(
+                        draw_list,
+                        p1,
+                        p2,
+                        rounding,
+                        ImDrawCornerFlags::All,
+                    ),
+                }
+            }
+            if self.filled {
+                sys::ImDrawList_PathFillConvex(draw_list, self.color.into())
+            } else {
+                sys::ImDrawList_PathStroke(
+                    draw_list,
+                    self.color.into(),
+                    self.closed,
+                    self.thickness,
+                )
+            }
+        }
+    }
+}
+
+/// Represents a polyline about to be drawn
+#[must_use = "should call .build() to draw the object"]
+pub struct Polyline<'ui> {
+    points: Vec<ImVec2>,
+    color: ImColor,
+    thickness: f32,
+    closed: bool,
+    filled: bool,
+    draw_list: &'ui WindowDrawList<'ui>,
+}
+
+impl<'ui> Polyline<'ui> {
+    fn new<I, P, C>(draw_list: &'ui WindowDrawList, points: I, color: C) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<ImVec2>,
+        C: Into<ImColor>,
+    {
+        Self {
+            points: points.into_iter().map(Into::into).collect(),
+            color: color.into(),
+            thickness: 1.0,
+            closed: false,
+            filled: false,
+            draw_list,
+        }
+    }
+
+    /// Set line's thickness (default to 1.0 pixel)
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Set to `true` to connect the last point to the first one (default to `false`).
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// Set to `true` to draw a filled polygon, which must be convex (default
+    /// to `false`).
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Draw the polyline on the window.
+    pub fn build(self) {
+        if self.filled {
+            unsafe {
+                sys::ImDrawList_AddConvexPolyFilled(
+                    self.draw_list.draw_list,
+                    self.points.as_ptr(),
+                    self.points.len() as c_int,
+                    self.color.into(),
+                )
+            }
+        } else {
+            unsafe {
+                sys::ImDrawList_AddPolyline(
+                    self.draw_list.draw_list,
+                    self.points.as_ptr(),
+                    self.points.len() as c_int,
+                    self.color.into(),
+                    self.closed,
+                    self.thickness,
+                )
+            }
+        }
+    }
+}
+
+/// Represents a quadrilateral about to be drawn
+#[must_use = "should call .build() to draw the object"]
+pub struct Quad<'ui> {
+    p1: ImVec2,
+    p2: ImVec2,
+    p3: ImVec2,
+    p4: ImVec2,
+    color: ImColor,
+    thickness: f32,
+    filled: bool,
+    draw_list: &'ui WindowDrawList<'ui>,
+}
+
+impl<'ui> Quad<'ui> {
+    fn new<P1, P2, P3, P4, C>(
+        draw_list: &'ui WindowDrawList,
+        p1: P1,
+        p2: P2,
+        p3: P3,
+        p4: P4,
+        c: C,
+    ) -> Self
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        P3: Into<ImVec2>,
+        P4: Into<ImVec2>,
+        C: Into<ImColor>,
+    {
+        Self {
+            p1: p1.into(),
+            p2: p2.into(),
+            p3: p3.into(),
+            p4: p4.into(),
+            color: c.into(),
+            thickness: 1.0,
+            filled: false,
+            draw_list,
+        }
+    }
+
+    /// Set quadrilateral's thickness (default to 1.0 pixel)
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Set to `true` to make a filled quadrilateral (default to `false`).
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Draw the quadrilateral on the window.
+    pub fn build(self) {
+        if self.filled {
+            unsafe {
+                sys::ImDrawList_AddQuadFilled(
+                    self.draw_list.draw_list,
+                    self.p1,
+                    self.p2,
+                    self.p3,
+                    self.p4,
+                    self.color.into(),
+                )
+            }
+        } else {
+            unsafe {
+                sys::ImDrawList_AddQuad(
+                    self.draw_list.draw_list,
+                    self.p1,
+                    self.p2,
+                    self.p3,
+                    self.p4,
+                    self.color.into(),
+                    self.thickness,
+                )
+            }
+        }
+    }
+}
+
+fn texture_ptr(texture_id: ImTexture) -> *mut c_void {
+    texture_id.id() as *mut c_void
+}
+
+/// Represents an image about to be drawn
+#[must_use = "should call .build() to draw the object"]
+pub struct DrawImage<'ui> {
+    texture_id: ImTexture,
+    p1: ImVec2,
+    p2: ImVec2,
+    uv1: ImVec2,
+    uv2: ImVec2,
+    color: ImColor,
+    rounding: f32,
+    flags: ImDrawCornerFlags,
+    draw_list: &'ui WindowDrawList<'ui>,
+}
+
+impl<'ui> DrawImage<'ui> {
+    fn new<P1, P2>(draw_list: &'ui WindowDrawList, texture_id: ImTexture, p1: P1, p2: P2) -> Self
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+    {
+        Self {
+            texture_id,
+            p1: p1.into(),
+            p2: p2.into(),
+            uv1: ImVec2::new(0.0, 0.0),
+            uv2: ImVec2::new(1.0, 1.0),
+            color: [1.0, 1.0, 1.0, 1.0].into(),
+            rounding: 0.0,
+            flags: ImDrawCornerFlags::All,
+            draw_list,
+        }
+    }
+
+    /// Set the texture coordinates of the upper-left corner (default to (0, 0)).
+    pub fn uv1<P>(mut self, uv1: P) -> Self
+    where
+        P: Into<ImVec2>,
+    {
+        self.uv1 = uv1.into();
+        self
+    }
+
+    /// Set the texture coordinates of the lower-right corner (default to (1, 1)).
+    pub fn uv2<P>(mut self, uv2: P) -> Self
+    where
+        P: Into<ImVec2>,
+    {
+        self.uv2 = uv2.into();
+        self
+    }
+
+    /// Set the color the image is multiplied with (default to white).
+    pub fn color<C>(mut self, color: C) -> Self
+    where
+        C: Into<ImColor>,
+    {
+        self.color = color.into();
+        self
+    }
+
+    /// Set image's corner rounding (default to 0.0: no rounding).
+    /// By default all corners are rounded if this value is set.
+    pub fn rounding(mut self, rounding: f32) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Set flag to indicate if image's top-left corner will be rounded.
+    pub fn round_top_left(mut self, value: bool) -> Self {
+        self.flags.set(ImDrawCornerFlags::TopLeft, value);
+        self
+    }
+
+    /// Set flag to indicate if image's top-right corner will be rounded.
+    pub fn round_top_right(mut self, value: bool) -> Self {
+        self.flags.set(ImDrawCornerFlags::TopRight, value);
+        self
+    }
+
+    /// Set flag to indicate if image's bottom-left corner will be rounded.
+    pub fn round_bot_left(mut self, value: bool) -> Self {
+        self.flags.set(ImDrawCornerFlags::BotLeft, value);
+        self
+    }
+
+    /// Set flag to indicate if image's bottom-right corner will be rounded.
+    pub fn round_bot_right(mut self, value: bool) -> Self {
+        self.flags.set(ImDrawCornerFlags::BotRight, value);
+        self
+    }
+
+    /// Draw the image on the window.
+    pub fn build(self) {
+        if self.rounding > 0.0 {
+            unsafe {
+                sys::ImDrawList_AddImageRounded(
+                    self.draw_list.draw_list,
+                    texture_ptr(self.texture_id),
+                    self.p1,
+                    self.p2,
+                    self.uv1,
+                    self.uv2,
+                    self.color.into(),
+                    self.rounding,
+                    self.flags,
+                )
+            }
+        } else {
+            unsafe {
+                sys::ImDrawList_AddImage(
+                    self.draw_list.draw_list,
+                    texture_ptr(self.texture_id),
+                    self.p1,
+                    self.p2,
+                    self.uv1,
+                    self.uv2,
+                    self.color.into(),
+                )
+            }
+        }
+    }
+}
+
+/// Represents an image mapped on a quadrilateral about to be drawn
+#[must_use = "should call .build() to draw the object"]
+pub struct DrawImageQuad<'ui> {
+    texture_id: ImTexture,
+    p1: ImVec2,
+    p2: ImVec2,
+    p3: ImVec2,
+    p4: ImVec2,
+    uv1: ImVec2,
+    uv2: ImVec2,
+    uv3: ImVec2,
+    uv4: ImVec2,
+    color: ImColor,
+    draw_list: &'ui WindowDrawList<'ui>,
+}
+
+impl<'ui> DrawImageQuad<'ui> {
+    fn new<P1, P2, P3, P4>(
+        draw_list: &'ui WindowDrawList,
+        texture_id: ImTexture,
+        p1: P1,
+        p2: P2,
+        p3: P3,
+        p4: P4,
+    ) -> Self
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        P3: Into<ImVec2>,
+        P4: Into<ImVec2>,
+    {
+        Self {
+            texture_id,
+            p1: p1.into(),
+            p2: p2.into(),
+            p3: p3.into(),
+            p4: p4.into(),
+            uv1: ImVec2::new(0.0, 0.0),
+            uv2: ImVec2::new(1.0, 0.0),
+            uv3: ImVec2::new(1.0, 1.0),
+            uv4: ImVec2::new(0.0, 1.0),
+            color: [1.0, 1.0, 1.0, 1.0].into(),
+            draw_list,
+        }
+    }
+
+    /// Set the texture coordinates of the 4 vertices (default to the corners
+    /// of the texture, clockwise from the upper-left corner).
+    pub fn uv<P1, P2, P3, P4>(mut self, uv1: P1, uv2: P2, uv3: P3, uv4: P4) -> Self
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        P3: Into<ImVec2>,
+        P4: Into<ImVec2>,
+    {
+        self.uv1 = uv1.into();
+        self.uv2 = uv2.into();
+        self.uv3 = uv3.into();
+        self.uv4 = uv4.into();
+        self
+    }
+
+    /// Set the color the image is multiplied with (default to white).
+    pub fn color<C>(mut self, color: C) -> Self
+    where
+        C: Into<ImColor>,
+    {
+        self.color = color.into();
+        self
+    }
+
+    /// Draw the image on the window.
+    pub fn build(self) {
+        unsafe {
+            sys::ImDrawList_AddImageQuad(
+                self.draw_list.draw_list,
+                texture_ptr(self.texture_id),
+                self.p1,
+                self.p2,
+                self.p3,
+                self.p4,
+                self.uv1,
+                self.uv2,
+                self.uv3,
+                self.uv4,
+                self.color.into(),
+            )
+        }
+    }
+}
+
+/// Writes the vertices and indices reserved with
+/// [`WindowDrawList::prim_reserve`].
+///
+/// Indices are relative to the first reserved vertex. The elements which are
+/// still unwritten when the writer is dropped are filled with degenerate
+/// triangles.
+#[must_use = "should write the reserved vertices and indices"]
+pub struct PrimWriter<'ui> {
+    idx_count: usize,
+    vtx_count: usize,
+    idx_written: usize,
+    vtx_written: usize,
+    vtx_base: usize,
+    texture_pushed: bool,
+    draw_list: &'ui WindowDrawList<'ui>,
+}
+
+impl<'ui> PrimWriter<'ui> {
+    fn new(
+        draw_list: &'ui WindowDrawList,
+        texture_id: Option<ImTexture>,
+        idx_count: usize,
+        vtx_count: usize,
+    ) -> Self {
+        assert!(
+            idx_count % 3 == 0,
+            "Index count ({}) must be a multiple of 3!",
+            idx_count
+        );
+        assert!(
+            vtx_count > 0 || idx_count == 0,
+            "Cannot reserve {} indices without vertices!",
+            idx_count
+        );
+        // The vertices of all channels are stored in the same buffer
+        let vtx_base = unsafe { (*draw_list.draw_list).vtx_buffer.size } as usize;
+        assert!(
+            vtx_base + vtx_count <= ImDrawIdx::max_value() as usize + 1,
+            "Cannot reserve {} vertices! The draw list already has {} vertices and uses 16-bit indices.",
+            vtx_count,
+            vtx_base
+        );
+        if let Some(texture_id) = texture_id {
+            unsafe { sys::ImDrawList_PushTextureID(draw_list.draw_list, texture_ptr(texture_id)) }
+        }
+        unsafe {
+            sys::ImDrawList_PrimReserve(draw_list.draw_list, idx_count as c_int, vtx_count as c_int)
+        }
+        Self {
+            idx_count,
+            vtx_count,
+            idx_written: 0,
+            vtx_written: 0,
+            vtx_base,
+            texture_pushed: texture_id.is_some(),
+            draw_list,
+        }
+    }
+
+    /// Number of vertices which can still be written.
+    pub fn remaining_vtx(&self) -> usize {
+        self.vtx_count - self.vtx_written
+    }
+
+    /// Number of indices which can still be written.
+    pub fn remaining_idx(&self) -> usize {
+        self.idx_count - self.idx_written
+    }
+
+    /// Texture coordinates of a white pixel of the font atlas, to draw
+    /// plain colors without a texture.
+    pub fn tex_uv_white_pixel(&self) -> (f32, f32) {
+        let uv = unsafe { sys::igGetFontTexUvWhitePixel_nonUDT2() };
+        uv.into()
+    }
+
+    /// Write the next vertex.
+    ///
+    /// Panic if all reserved vertices are already written.
+    pub fn write_vtx(&mut self, vtx: ImDrawVert) {
+        self.reserve(0, 1);
+        unsafe { sys::ImDrawList_PrimWriteVtx(self.draw_list.draw_list, vtx.pos, vtx.uv, vtx.col) }
+    }
+
+    /// Write the next index, relative to the first reserved vertex.
+    ///
+    /// Panic if all reserved indices are already written, or if `idx` is
+    /// not the index of a reserved vertex.
+    pub fn write_idx(&mut self, idx: ImDrawIdx) {
+        assert!(
+            (idx as usize) < self.vtx_count,
+            "Index {} is out of the {} reserved vertices!",
+            idx,
+            self.vtx_count
+        );
+        self.reserve(1, 0);
+        let idx = (self.vtx_base + idx as usize) as ImDrawIdx;
+        unsafe { sys::ImDrawList_PrimWriteIdx(self.draw_list.draw_list, idx) }
+    }
+
+    /// Write a rectangle from 4 vertices and 6 indices, whose upper-left
+    /// corner is at point `p1` and lower-right corner is at point `p2`,
+    /// with the texture coordinates `uv1` and `uv2` at these corners.
+    ///
+    /// Panic if fewer vertices or indices remain.
+    pub fn rect_uv<P1, P2, UV1, UV2, C>(&mut self, p1: P1, p2: P2, uv1: UV1, uv2: UV2, color: C)
+    where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        UV1: Into<ImVec2>,
+        UV2: Into<ImVec2>,
+        C: Into<ImColor>,
+    {
+        self.reserve(6, 4);
+        unsafe {
+            sys::ImDrawList_PrimRectUV(
+                self.draw_list.draw_list,
+                p1.into(),
+                p2.into(),
+                uv1.into(),
+                uv2.into(),
+                color.into().into(),
+            )
+        }
+    }
+
+    /// Write a quadrilateral from 4 vertices and 6 indices, with the
+    /// texture coordinates `uv1` to `uv4` at the vertices `p1` to `p4`.
+    ///
+    /// Panic if fewer vertices or indices remain.
+    #[allow(clippy::too_many_arguments)]
+    pub fn quad_uv<P1, P2, P3, P4, UV1, UV2, UV3, UV4, C>(
+        &mut self,
+        p1: P1,
+        p2: P2,
+        p3: P3,
+        p4: P4,
+        uv1: UV1,
+        uv2: UV2,
+        uv3: UV3,
+        uv4: UV4,
+        color: C,
+    ) where
+        P1: Into<ImVec2>,
+        P2: Into<ImVec2>,
+        P3: Into<ImVec2>,
+        P4: Into<ImVec2>,
+        UV1: Into<ImVec2>,
+        UV2: Into<ImVec2>,
+        UV3: Into<ImVec2>,
+        UV4: Into<ImVec2>,
+        C: Into<ImColor>,
+    {
+        self.reserve(6, 4);
+        unsafe {
+            sys::ImDrawList_PrimQuadUV(
+                self.draw_list.draw_list,
+                p1.into(),
+                p2.into(),
+                p3.into(),
+                p4.into(),
+                uv1.into(),
+                uv2.into(),
+                uv3.into(),
+                uv4.into(),
+                color.into().into(),
+            )
+        }
+    }
+
+    fn reserve(&mut self, idx_count: usize, vtx_count: usize) {
+        assert!(
+            idx_count <= self.remaining_idx() && vtx_count <= self.remaining_vtx(),
+            "Cannot write {} indices and {} vertices! Only {} indices and {} vertices remain.",
+            idx_count,
+            vtx_count,
+            self.remaining_idx(),
+            self.remaining_vtx()
+        );
+        self.idx_written += idx_count;
+        self.vtx_written += vtx_count;
+    }
+}
+
+impl<'ui> Drop for PrimWriter<'ui> {
+    fn drop(&mut self) {
+        let draw_list = self.draw_list.draw_list;
+        let zero = ImVec2::new(0.0, 0.0);
+        unsafe {
+            for _ in self.vtx_written..self.vtx_count {
+                sys::ImDrawList_PrimWriteVtx(draw_list, zero, zero, 0);
+            }
+            for _ in self.idx_written..self.idx_count {
+                sys::ImDrawList_PrimWriteIdx(draw_list, self.vtx_base as ImDrawIdx);
+            }
+            if self.texture_pushed {
+                sys::ImDrawList_PopTextureID(draw_list);
+            }
+        }
+    }
+}